@@ -0,0 +1,202 @@
+use anyhow::Context;
+use aoc_runner::registry;
+use aoc_solution::Runner;
+use aqa_aoc::bench::{self, Baseline, DayBench, Stats, BASELINE_FILE};
+use aqa_aoc::cfg::AocCfg;
+use clap::{Parser, Subcommand};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Runs the solutions registered in this crate, usually through `aqa_aoc run` and `aqa_aoc bench`
+#[derive(Debug, Parser)]
+#[command(name = "aoc_runner")]
+struct Cli {
+	/// Event to work with, the year from the config file when not set, or the year of the days
+	/// in the workspace root without a config file
+	#[arg(long, global = true)]
+	year: Option<u32>,
+	/// Config file to use
+	#[arg(long, global = true, default_value = "aoc_cfg.toml")]
+	config: PathBuf,
+	#[command(subcommand)]
+	command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+	/// Runs the solution of a day
+	Run {
+		#[arg(long, value_parser = clap::value_parser!(u32).range(1..=25))]
+		day: u32,
+		/// Run only this part
+		#[arg(long, value_parser = clap::value_parser!(u32).range(1..=2))]
+		part: Option<u32>,
+	},
+	/// Times parsing and both parts of every day against its input
+	Bench {
+		/// Benchmark only this day
+		#[arg(long, value_parser = clap::value_parser!(u32).range(1..=25))]
+		day: Option<u32>,
+		/// Runs of every day
+		#[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+		iterations: u32,
+		/// Slowdown of a median over the baseline reported as a regression, in percent
+		#[arg(long, default_value_t = bench::DEFAULT_THRESHOLD)]
+		threshold: f64,
+		/// Save the results as the new baseline
+		#[arg(long)]
+		save: bool,
+	},
+}
+
+fn main() -> anyhow::Result<()> {
+	let cli = Cli::parse();
+	let year = match cli.year {
+		Some(v) => v,
+		None if cli.config.exists() => AocCfg::load(&cli.config)?.year,
+		None => {
+			registry::default_year().context("no day is registered, pass the year with --year")?
+		}
+	};
+
+	match cli.command {
+		Command::Run { day, part } => run_day(year, day, part),
+		Command::Bench {
+			day,
+			iterations,
+			threshold,
+			save,
+		} => bench_days(year, day, iterations, threshold, save),
+	}
+}
+
+fn run_day(year: u32, day: u32, part: Option<u32>) -> anyhow::Result<()> {
+	let (runner, member) = registry::find(year, day)
+		.with_context(|| format!("day {} of {} is not registered", day, year))?;
+
+	let input_path = Path::new(member).join("input.txt");
+	let input = fs::read_to_string(&input_path)
+		.with_context(|| format!("failed to read {}", input_path.display()))?;
+
+	let run = runner(&input, part)?;
+
+	println!("Day {}", day);
+	println!("Parsing: {:?}", run.parse_time);
+	if let Some(part_1) = run.part_1 {
+		println!("Part 1: {} ({:?})", part_1.answer, part_1.time);
+	}
+	if let Some(part_2) = run.part_2 {
+		println!("Part 2: {} ({:?})", part_2.answer, part_2.time);
+	}
+
+	Ok(())
+}
+
+/// Benchmarks the registered days of the year with an input, or only `day` when set
+fn bench_days(
+	year: u32,
+	day: Option<u32>,
+	iterations: u32,
+	threshold: f64,
+	save: bool,
+) -> anyhow::Result<()> {
+	if cfg!(debug_assertions) {
+		eprintln!("Warning: aoc_runner was built without optimizations, use cargo run --release");
+	}
+
+	if let Some(day) = day {
+		if registry::find(year, day).is_none() {
+			anyhow::bail!("day {} of {} is not registered", day, year);
+		}
+	}
+	let solutions = registry::SOLUTIONS
+		.iter()
+		.filter(|(solution_year, solution_day, _, _)| {
+			*solution_year == year && day.is_none_or(|day| day == *solution_day)
+		});
+
+	let mut benches = Vec::new();
+	for (_, day, member, runner) in solutions {
+		let input_path = Path::new(member).join("input.txt");
+		let input = match fs::read_to_string(&input_path) {
+			Ok(v) => v,
+			Err(_) => {
+				println!("Skipping day {}, {} is missing", day, input_path.display());
+				continue;
+			}
+		};
+
+		println!("Benchmarking day {}...", day);
+		let bench = bench_day(*runner, year, *day, &input, iterations)
+			.with_context(|| format!("day {} failed", day))?;
+		benches.push(bench);
+	}
+
+	let baseline_path = Path::new(BASELINE_FILE);
+	let baseline = Baseline::load(baseline_path)?;
+	println!();
+	print!("{}", bench::render(&benches, baseline.as_ref()));
+
+	let regressions = baseline
+		.as_ref()
+		.map(|baseline| bench::regressions(baseline, &benches, threshold))
+		.unwrap_or_default();
+	for regression in &regressions {
+		println!(
+			"Regression: day {} {} {:.2?} -> {:.2?} ({:+.1}%)",
+			regression.day,
+			regression.step,
+			Duration::from_nanos(regression.baseline),
+			Duration::from_nanos(regression.current),
+			regression.slowdown()
+		);
+	}
+
+	if save {
+		let mut baseline = baseline.unwrap_or_default();
+		baseline.update(&benches);
+		baseline.save(baseline_path)?;
+		println!("Baseline saved to {}", BASELINE_FILE);
+	}
+
+	if !regressions.is_empty() {
+		anyhow::bail!(
+			"{} regression(s) above the {}% threshold",
+			regressions.len(),
+			threshold
+		);
+	}
+
+	Ok(())
+}
+
+/// Runs the solution `iterations` times on `input`
+fn bench_day(
+	runner: Runner,
+	year: u32,
+	day: u32,
+	input: &str,
+	iterations: u32,
+) -> anyhow::Result<DayBench> {
+	let iterations = iterations.max(1);
+	let mut times = [Vec::new(), Vec::new(), Vec::new()];
+	for _ in 0..iterations {
+		let run = runner(input, None)?;
+		times[0].push(run.parse_time);
+		// SAFETY: both parts run when no part is requested
+		times[1].push(run.part_1.unwrap().time);
+		times[2].push(run.part_2.unwrap().time);
+	}
+
+	// SAFETY: there is at least one iteration
+	let [parse, part_1, part_2] = times.map(|times| Stats::from_times(&times).unwrap());
+	Ok(DayBench {
+		year,
+		day,
+		iterations,
+		parse,
+		part_1,
+		part_2,
+	})
+}
@@ -0,0 +1,55 @@
+use aoc_solution::{run, Runner};
+
+/// Every solved day by year with its crate directory, new days are added by `aqa_aoc new`
+pub const SOLUTIONS: &[(u32, u32, &str, Runner)] = &[
+	(2020, 1, "day_1", run::<day_1::Day1>),
+	(2020, 2, "day_2", run::<day_2::Day2>),
+	(2020, 3, "day_3", run::<day_3::Day3>),
+	(2020, 4, "day_4", run::<day_4::Day4>),
+	(2020, 5, "day_5", run::<day_5::Day5>),
+	(2020, 6, "day_6", run::<day_6::Day6>),
+	(2020, 7, "day_7", run::<day_7::Day7>),
+	(2020, 8, "day_8", run::<day_8::Day8>),
+	(2020, 9, "day_9", run::<day_9::Day9>),
+	(2020, 10, "day_10", run::<day_10::Day10>),
+	(2020, 11, "day_11", run::<day_11::Day11>),
+	(2020, 12, "day_12", run::<day_12::Day12>),
+	(2020, 13, "day_13", run::<day_13::Day13>),
+	(2020, 14, "day_14", run::<day_14::Day14>),
+	(2020, 15, "day_15", run::<day_15::Day15>),
+	(2020, 16, "day_16", run::<day_16::Day16>),
+	(2020, 17, "day_17", run::<day_17::Day17>),
+	(2020, 18, "day_18", run::<day_18::Day18>),
+	// New days are registered above this line by aqa_aoc
+];
+
+/// Runner of a day and the directory of its crate
+pub fn find(year: u32, day: u32) -> Option<(Runner, &'static str)> {
+	SOLUTIONS
+		.iter()
+		.find(|(solution_year, solution_day, _, _)| *solution_year == year && *solution_day == day)
+		.map(|(_, _, member, runner)| (*runner, *member))
+}
+
+/// Year of the days in the workspace root, the year from the config file when they were added.
+/// The latest registered year when every day is in a `yYYYY` directory.
+pub fn default_year() -> Option<u32> {
+	SOLUTIONS
+		.iter()
+		.find(|(_, _, member, _)| !member.contains('/'))
+		.or_else(|| SOLUTIONS.iter().max_by_key(|(year, _, _, _)| *year))
+		.map(|(year, _, _, _)| *year)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{default_year, find};
+
+	#[test]
+	fn find_day() {
+		assert!(find(2020, 1).is_some());
+		assert!(find(2020, 0).is_none());
+		assert!(find(2019, 1).is_none());
+		assert_eq!(default_year(), Some(2020));
+	}
+}
@@ -0,0 +1,70 @@
+use aoc_runner::registry;
+use aqa_aoc::answers::{Answers, ANSWERS_FILE};
+use std::fs;
+use std::path::Path;
+
+/// Runs every registered day on its input and compares the answers with the ledger.
+///
+/// Days without an input or without recorded answers are skipped, run with `--nocapture`
+/// to see which ones.
+#[test]
+fn registered_days_match_the_ledger() {
+	let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+	let answers = Answers::load(&root.join(ANSWERS_FILE)).unwrap();
+
+	let mut failures = Vec::new();
+	for (year, day, member, runner) in registry::SOLUTIONS {
+		let expected = [1, 2].map(|part| answers.get(*year, *day, part));
+		let part = match expected {
+			[None, None] => {
+				println!(
+					"Skipping {} day {}: no answers in {}",
+					year, day, ANSWERS_FILE
+				);
+				continue;
+			}
+			[Some(_), None] => Some(1),
+			[None, Some(_)] => Some(2),
+			[Some(_), Some(_)] => None,
+		};
+
+		let input_path = root.join(member).join("input.txt");
+		let input = match fs::read_to_string(&input_path) {
+			Ok(v) => v,
+			Err(_) => {
+				println!(
+					"Skipping {} day {}: {} is missing",
+					year,
+					day,
+					input_path.display()
+				);
+				continue;
+			}
+		};
+
+		let run = match runner(&input, part) {
+			Ok(v) => v,
+			Err(e) => {
+				failures.push(format!("{} day {} failed: {:#}", year, day, e));
+				continue;
+			}
+		};
+		let actual = [run.part_1, run.part_2].map(|part| part.map(|part| part.answer));
+		for (idx, (expected, actual)) in expected.iter().zip(&actual).enumerate() {
+			if let (Some(expected), Some(actual)) = (expected, actual) {
+				if expected != actual {
+					failures.push(format!(
+						"{} day {} part {}: expected {}, got {}",
+						year,
+						day,
+						idx + 1,
+						expected,
+						actual
+					));
+				}
+			}
+		}
+	}
+
+	assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
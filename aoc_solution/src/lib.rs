@@ -0,0 +1,407 @@
+use anyhow::{bail, Context, Result};
+use std::fmt::Display;
+use std::io::Read;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Solution of a single day, implemented by every `day_N` crate
+pub trait Solution {
+	/// Parsed puzzle input, may borrow from the raw input
+	type Input<'a>;
+	type Part1: Display;
+	type Part2: Display;
+
+	fn parse(input: &str) -> Result<Self::Input<'_>>;
+	fn part_1(input: &Self::Input<'_>) -> Result<Self::Part1>;
+	fn part_2(input: &Self::Input<'_>) -> Result<Self::Part2>;
+}
+
+#[derive(Debug, Clone)]
+pub struct PartRun {
+	pub answer: String,
+	pub time: Duration,
+}
+
+/// Answers and timings of a single run, parts that were not requested are `None`
+#[derive(Debug, Clone)]
+pub struct Run {
+	pub parse_time: Duration,
+	pub part_1: Option<PartRun>,
+	pub part_2: Option<PartRun>,
+}
+
+/// Type erased [run], used to dispatch to any day through a registry
+pub type Runner = fn(&str, Option<u32>) -> Result<Run>;
+
+/// Parses the input and runs the requested part, or both of them when `part` is `None`
+pub fn run<S: Solution>(input: &str, part: Option<u32>) -> Result<Run> {
+	let start = Instant::now();
+	let parsed = S::parse(input)?;
+	let parse_time = start.elapsed();
+
+	let part_1 = match part {
+		None | Some(1) => {
+			let start = Instant::now();
+			let answer = S::part_1(&parsed)?.to_string();
+			Some(PartRun {
+				answer,
+				time: start.elapsed(),
+			})
+		}
+		_ => None,
+	};
+
+	let part_2 = match part {
+		None | Some(2) => {
+			let start = Instant::now();
+			let answer = S::part_2(&parsed)?.to_string();
+			Some(PartRun {
+				answer,
+				time: start.elapsed(),
+			})
+		}
+		_ => None,
+	};
+
+	Ok(Run {
+		parse_time,
+		part_1,
+		part_2,
+	})
+}
+
+/// Environment variable with the path of the input, `-` reads it from stdin
+pub const INPUT_ENV: &str = "AOC_INPUT";
+
+/// Where a day binary reads its input from
+#[derive(Debug, PartialEq, Eq)]
+pub enum InputSource {
+	Stdin,
+	File(PathBuf),
+}
+
+impl InputSource {
+	/// Picks the first argument, then `AOC_INPUT`, then `input.txt` in `manifest_dir`;
+	/// `-` stands for stdin
+	pub fn resolve(arg: Option<String>, env: Option<String>, manifest_dir: &str) -> InputSource {
+		match arg.or(env).filter(|path| !path.is_empty()) {
+			Some(path) if path == "-" => InputSource::Stdin,
+			Some(path) => InputSource::File(path.into()),
+			None => InputSource::File(PathBuf::from(manifest_dir).join("input.txt")),
+		}
+	}
+
+	pub fn read(&self) -> Result<String> {
+		match self {
+			InputSource::Stdin => {
+				let mut input = String::new();
+				std::io::stdin()
+					.read_to_string(&mut input)
+					.context("failed to read the input from stdin")?;
+				Ok(input)
+			}
+			InputSource::File(path) => std::fs::read_to_string(path)
+				.with_context(|| format!("failed to read {}", path.display())),
+		}
+	}
+}
+
+const USAGE: &str = "usage: [INPUT | - | --example[=K] | --all-examples]";
+
+/// What a day binary was asked to run
+#[derive(Debug, PartialEq, Eq)]
+pub enum Mode {
+	Input(InputSource),
+	/// Runs `examples/example_K.txt`
+	Example(u32),
+	/// Runs every example and prints a table
+	AllExamples,
+}
+
+impl Mode {
+	/// Parses the arguments of a day binary, without the program name
+	pub fn parse(args: &[String], env: Option<String>, manifest_dir: &str) -> Result<Mode> {
+		let arg = match args {
+			[] => return Ok(Mode::Input(InputSource::resolve(None, env, manifest_dir))),
+			[arg] => arg,
+			_ => bail!("too many arguments, {}", USAGE),
+		};
+
+		if arg == "--example" {
+			return Ok(Mode::Example(1));
+		}
+		if arg == "--all-examples" {
+			return Ok(Mode::AllExamples);
+		}
+		if let Some(example) = arg.strip_prefix("--example=") {
+			return example
+				.parse()
+				.ok()
+				.filter(|example| *example > 0)
+				.map(Mode::Example)
+				.with_context(|| format!("invalid example number \"{}\", {}", example, USAGE));
+		}
+		if arg.starts_with("--") {
+			bail!("unknown option {}, {}", arg, USAGE);
+		}
+
+		Ok(Mode::Input(InputSource::resolve(
+			Some(arg.clone()),
+			env,
+			manifest_dir,
+		)))
+	}
+}
+
+/// Entry point of the day binaries, `manifest_dir` is the `CARGO_MANIFEST_DIR` of the crate
+pub fn main<S: Solution>(manifest_dir: &str) -> Result<()> {
+	let args = std::env::args().skip(1).collect::<Vec<_>>();
+	match Mode::parse(&args, std::env::var(INPUT_ENV).ok(), manifest_dir)? {
+		Mode::Input(source) => print_answers::<S>(&source.read()?),
+		Mode::Example(example) => {
+			let path = Path::new(manifest_dir)
+				.join("examples")
+				.join(format!("example_{}.txt", example));
+			let input = std::fs::read_to_string(&path)
+				.with_context(|| format!("failed to read {}", path.display()))?;
+
+			println!("Example {} ({})", example, path.display());
+			let answers = example_answers::<S>(&input);
+			for (part, answer) in (1..).zip(&answers) {
+				let (Ok(answer) | Err(answer)) = answer;
+				println!("Part {} (example {}): {}", part, example, answer);
+			}
+			if answers.iter().any(Result::is_err) {
+				bail!("example {} failed", example);
+			}
+			Ok(())
+		}
+		Mode::AllExamples => {
+			let examples = example_files(Path::new(manifest_dir))?;
+			if examples.is_empty() {
+				bail!("no examples in {}/examples", manifest_dir);
+			}
+
+			let mut inputs = Vec::new();
+			for (example, path) in examples {
+				let input = std::fs::read_to_string(&path)
+					.with_context(|| format!("failed to read {}", path.display()))?;
+				inputs.push((example, input));
+			}
+
+			// One line per panic instead of a backtrace in front of the table
+			let hook = panic::take_hook();
+			panic::set_hook(Box::new(|info| eprintln!("{}", info)));
+			let rows = inputs
+				.iter()
+				.map(|(example, input)| {
+					let answers = example_answers::<S>(input);
+					(
+						*example,
+						answers.map(|answer| answer.unwrap_or_else(|cell| cell)),
+					)
+				})
+				.collect::<Vec<_>>();
+			panic::set_hook(hook);
+
+			print!("{}", render_examples(&rows));
+			Ok(())
+		}
+	}
+}
+
+/// Returns the `examples/example_K.txt` files of a crate ordered by `K`
+fn example_files(crate_dir: &Path) -> Result<Vec<(u32, PathBuf)>> {
+	let dir = crate_dir.join("examples");
+	let entries = match std::fs::read_dir(&dir) {
+		Ok(v) => v,
+		Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+		Err(e) => return Err(e).with_context(|| format!("failed to read {}", dir.display())),
+	};
+
+	let mut examples = Vec::new();
+	for entry in entries {
+		let path = entry?.path();
+		let example = path
+			.file_name()
+			.and_then(|name| name.to_str())
+			.and_then(|name| name.strip_prefix("example_"))
+			.and_then(|name| name.strip_suffix(".txt"))
+			.and_then(|example| example.parse::<u32>().ok());
+		if let Some(example) = example {
+			examples.push((example, path));
+		}
+	}
+	examples.sort();
+
+	Ok(examples)
+}
+
+/// Answers of both parts, examples are often meant for a single part so each one fails on its
+/// own. `Err` is what to show instead of the answer, a panic's message still goes to stderr.
+fn example_answers<S: Solution>(input: &str) -> [Result<String, String>; 2] {
+	let parsed = match catch_panic(|| S::parse(input)) {
+		Some(Ok(v)) => v,
+		Some(Err(e)) => return [Err(format!("error: {}", e)), Err("-".to_string())],
+		None => return [Err("panicked".to_string()), Err("-".to_string())],
+	};
+	[
+		catch_panic(|| S::part_1(&parsed).map(|answer| answer.to_string())),
+		catch_panic(|| S::part_2(&parsed).map(|answer| answer.to_string())),
+	]
+	.map(|answer| match answer {
+		Some(Ok(answer)) => Ok(answer),
+		Some(Err(e)) => Err(format!("error: {}", e)),
+		None => Err("panicked".to_string()),
+	})
+}
+
+/// `None` when `f` panicked
+fn catch_panic<T>(f: impl FnOnce() -> T) -> Option<T> {
+	panic::catch_unwind(AssertUnwindSafe(f)).ok()
+}
+
+fn render_examples(rows: &[(u32, [String; 2])]) -> String {
+	let width = rows
+		.iter()
+		.map(|(_, answers)| answers[0].len())
+		.chain(Some("Part 1".len()))
+		.max()
+		.unwrap_or(0);
+
+	let mut table = format!("Example  {:1$}  Part 2\n", "Part 1", width);
+	for (example, [part_1, part_2]) in rows {
+		table.push_str(&format!(
+			"{:>7}  {:3$}  {}\n",
+			example, part_1, part_2, width
+		));
+	}
+	table
+}
+
+/// Runs both parts and prints each answer as soon as it's known, with the time the part took
+/// like the runner does
+pub fn print_answers<S: Solution>(input: &str) -> Result<()> {
+	let parsed = S::parse(input)?;
+
+	let start = Instant::now();
+	let answer = S::part_1(&parsed)?;
+	println!("Part 1: {} ({:?})", answer, start.elapsed());
+
+	let start = Instant::now();
+	let answer = S::part_2(&parsed)?;
+	println!("Part 2: {} ({:?})", answer, start.elapsed());
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{example_answers, render_examples, InputSource, Mode, Solution};
+	use std::path::PathBuf;
+
+	#[test]
+	fn input_source_precedence() {
+		let resolve = |arg: Option<&str>, env: Option<&str>| {
+			InputSource::resolve(arg.map(Into::into), env.map(Into::into), "/ws/day_1")
+		};
+
+		assert_eq!(
+			resolve(None, None),
+			InputSource::File(PathBuf::from("/ws/day_1/input.txt"))
+		);
+		assert_eq!(
+			resolve(None, Some("other.txt")),
+			InputSource::File(PathBuf::from("other.txt"))
+		);
+		assert_eq!(
+			resolve(Some("arg.txt"), Some("other.txt")),
+			InputSource::File(PathBuf::from("arg.txt"))
+		);
+		assert_eq!(resolve(Some("-"), None), InputSource::Stdin);
+		assert_eq!(resolve(None, Some("-")), InputSource::Stdin);
+		assert_eq!(
+			resolve(None, Some("")),
+			InputSource::File(PathBuf::from("/ws/day_1/input.txt"))
+		);
+	}
+
+	#[test]
+	fn modes() {
+		let parse = |args: &[&str]| {
+			let args = args.iter().map(ToString::to_string).collect::<Vec<_>>();
+			Mode::parse(&args, None, "/ws/day_1")
+		};
+
+		assert_eq!(
+			parse(&[]).unwrap(),
+			Mode::Input(InputSource::File(PathBuf::from("/ws/day_1/input.txt")))
+		);
+		assert_eq!(parse(&["-"]).unwrap(), Mode::Input(InputSource::Stdin));
+		assert_eq!(parse(&["--example"]).unwrap(), Mode::Example(1));
+		assert_eq!(parse(&["--example=3"]).unwrap(), Mode::Example(3));
+		assert_eq!(parse(&["--all-examples"]).unwrap(), Mode::AllExamples);
+
+		assert!(parse(&["--example=0"]).is_err());
+		assert!(parse(&["--example=x"]).is_err());
+		assert!(parse(&["--examples"]).is_err());
+		assert!(parse(&["a.txt", "b.txt"]).is_err());
+	}
+
+	#[test]
+	fn examples_table() {
+		let rows = [
+			(1, ["514579".to_string(), "241861950".to_string()]),
+			(2, ["error: no answer".to_string(), "-".to_string()]),
+		];
+		assert_eq!(
+			render_examples(&rows),
+			"Example  Part 1            Part 2
+      1  514579            241861950
+      2  error: no answer  -
+"
+		);
+	}
+
+	struct Lookup;
+
+	impl Solution for Lookup {
+		type Input<'a> = Vec<u32>;
+		type Part1 = u32;
+		type Part2 = u32;
+
+		fn parse(input: &str) -> anyhow::Result<Vec<u32>> {
+			Ok(input
+				.split_whitespace()
+				.map(|n| n.parse())
+				.collect::<Result<_, _>>()?)
+		}
+
+		fn part_1(input: &Vec<u32>) -> anyhow::Result<u32> {
+			Ok(input[3])
+		}
+
+		fn part_2(input: &Vec<u32>) -> anyhow::Result<u32> {
+			Ok(input.iter().sum())
+		}
+	}
+
+	#[test]
+	fn panicking_example_part() {
+		assert_eq!(
+			example_answers::<Lookup>("1 2 3 4"),
+			[Ok("4".to_string()), Ok("10".to_string())]
+		);
+		assert_eq!(
+			example_answers::<Lookup>("1 2"),
+			[Err("panicked".to_string()), Ok("3".to_string())]
+		);
+		assert_eq!(
+			example_answers::<Lookup>("x"),
+			[
+				Err("error: invalid digit found in string".to_string()),
+				Err("-".to_string())
+			]
+		);
+	}
+}
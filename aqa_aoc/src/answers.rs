@@ -0,0 +1,193 @@
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use toml_edit::{DocumentMut, Item, Table};
+
+/// Ledger of answers accepted by the site, kept at the workspace root
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+#[derive(Debug, Error)]
+pub enum AnswersError {
+	#[error("failed to read {0}")]
+	Read(PathBuf, #[source] io::Error),
+	#[error("failed to write {0}")]
+	Write(PathBuf, #[source] io::Error),
+	#[error("failed to parse the answers")]
+	Parse(#[from] toml_edit::TomlError),
+	#[error("{0} in the answers is not a table")]
+	NotATable(String),
+	#[error("{0} in the answers is not a string or an integer")]
+	NotAnAnswer(String),
+}
+
+/// Answers by year and day:
+///
+/// ```toml
+/// [2020.day_1]
+/// part_1 = "1006875"
+/// part_2 = "165026160"
+/// ```
+pub struct Answers {
+	doc: DocumentMut,
+}
+
+impl Answers {
+	/// Parses the ledger, every answer has to be a string or an integer
+	pub fn parse(answers: &str) -> Result<Answers, AnswersError> {
+		let doc = answers.parse::<DocumentMut>()?;
+		for (year, days) in doc.iter() {
+			let days = days
+				.as_table_like()
+				.ok_or_else(|| AnswersError::NotATable(year.to_string()))?;
+			for (day, parts) in days.iter() {
+				let parts = parts
+					.as_table_like()
+					.ok_or_else(|| AnswersError::NotATable(format!("{}.{}", year, day)))?;
+				for (part, answer) in parts.iter() {
+					if answer_text(answer).is_none() {
+						return Err(AnswersError::NotAnAnswer(format!(
+							"{}.{}.{}",
+							year, day, part
+						)));
+					}
+				}
+			}
+		}
+		Ok(Answers { doc })
+	}
+
+	/// Loads the ledger, a missing file is an empty ledger
+	pub fn load(path: &Path) -> Result<Answers, AnswersError> {
+		match fs::read_to_string(path) {
+			Ok(v) => Answers::parse(&v),
+			Err(e) if e.kind() == ErrorKind::NotFound => Answers::parse(""),
+			Err(e) => Err(AnswersError::Read(path.to_owned(), e)),
+		}
+	}
+
+	pub fn get(&self, year: u32, day: u32, part: u32) -> Option<String> {
+		let answer = self
+			.doc
+			.get(&year.to_string())?
+			.get(format!("day_{}", day))?
+			.get(format!("part_{}", part))?;
+		answer_text(answer)
+	}
+
+	/// Records an answer, keeping the comments and formatting of the ledger
+	pub fn set(
+		&mut self,
+		year: u32,
+		day: u32,
+		part: u32,
+		answer: &str,
+	) -> Result<(), AnswersError> {
+		let year_key = year.to_string();
+		let year = self
+			.doc
+			.entry(&year_key)
+			.or_insert_with(|| {
+				let mut table = Table::new();
+				table.set_implicit(true);
+				Item::Table(table)
+			})
+			.as_table_like_mut()
+			.ok_or_else(|| AnswersError::NotATable(year_key.clone()))?;
+
+		let day_key = format!("day_{}", day);
+		let day = year
+			.entry(&day_key)
+			.or_insert(Item::Table(Table::new()))
+			.as_table_like_mut()
+			.ok_or_else(|| AnswersError::NotATable(format!("{}.{}", year_key, day_key)))?;
+		day.insert(&format!("part_{}", part), toml_edit::value(answer));
+		Ok(())
+	}
+
+	pub fn save(&self, path: &Path) -> Result<(), AnswersError> {
+		fs::write(path, self.doc.to_string()).map_err(|e| AnswersError::Write(path.to_owned(), e))
+	}
+}
+
+/// Answers are written as strings, integers are accepted for ledgers edited by hand
+fn answer_text(answer: &Item) -> Option<String> {
+	let answer = answer.as_value()?;
+	answer
+		.as_str()
+		.map(ToOwned::to_owned)
+		.or_else(|| answer.as_integer().map(|answer| answer.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Answers, AnswersError};
+
+	#[test]
+	fn answers_by_year_and_day() {
+		let answers = Answers::parse(
+			"[2020.day_1]
+part_1 = \"1006875\"
+part_2 = \"165026160\"
+
+[2020.day_2]
+part_1 = \"560\"
+",
+		)
+		.unwrap();
+
+		assert_eq!(answers.get(2020, 1, 2).as_deref(), Some("165026160"));
+		assert_eq!(answers.get(2020, 2, 1).as_deref(), Some("560"));
+		assert_eq!(answers.get(2020, 2, 2), None);
+		assert_eq!(answers.get(2019, 1, 1), None);
+	}
+
+	#[test]
+	fn records_answers() {
+		let ledger = "# Edited by hand\n[2020.day_1]\npart_1 = \"514579\" # example\n";
+		let mut answers = Answers::parse(ledger).unwrap();
+		answers.set(2020, 1, 2, "241861950").unwrap();
+		answers.set(2020, 2, 1, "2").unwrap();
+		answers.set(2021, 1, 1, "7").unwrap();
+
+		assert_eq!(
+			answers.doc.to_string(),
+			format!(
+				"{}part_2 = \"241861950\"\n\n[2020.day_2]\npart_1 = \"2\"\n\n[2021.day_1]\npart_1 = \"7\"\n",
+				ledger
+			)
+		);
+		assert_eq!(answers.get(2020, 1, 1).as_deref(), Some("514579"));
+	}
+
+	#[test]
+	fn hand_edited_ledger() {
+		let mut answers = Answers::parse("[2020.day_1]\npart_1 = 514579\n").unwrap();
+		assert_eq!(answers.get(2020, 1, 1).as_deref(), Some("514579"));
+		answers.set(2020, 1, 2, "241861950").unwrap();
+
+		assert!(matches!(
+			Answers::parse("[2020.day_1]\npart_1 = 5.5\n"),
+			Err(AnswersError::NotAnAnswer(key)) if key == "2020.day_1.part_1"
+		));
+		assert!(matches!(
+			Answers::parse("2020 = \"x\"\n"),
+			Err(AnswersError::NotATable(key)) if key == "2020"
+		));
+
+		// Setting doesn't rely on the checks of parse
+		let mut answers = Answers::parse("").unwrap();
+		answers.doc["2020"] = toml_edit::value("x");
+		assert!(matches!(
+			answers.set(2020, 1, 1, "1"),
+			Err(AnswersError::NotATable(key)) if key == "2020"
+		));
+	}
+
+	#[test]
+	fn missing_ledger_is_empty() {
+		let dir = tempfile::tempdir().unwrap();
+		let answers = Answers::load(&dir.path().join("answers.toml")).unwrap();
+		assert_eq!(answers.get(2020, 1, 1), None);
+	}
+}
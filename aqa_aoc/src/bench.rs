@@ -0,0 +1,290 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use thiserror::Error;
+
+/// Benchmark results of a previous run, kept at the workspace root
+pub const BASELINE_FILE: &str = "bench_baseline.json";
+
+/// Slowdown of a median over the baseline reported as a regression, in percent
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// Median, min and max of the timings of one step, in nanoseconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+	pub median: u64,
+	pub min: u64,
+	pub max: u64,
+}
+
+impl Stats {
+	/// Returns `None` for no timings
+	pub fn from_times(times: &[Duration]) -> Option<Stats> {
+		let mut nanos = times
+			.iter()
+			.map(|time| time.as_nanos() as u64)
+			.collect::<Vec<_>>();
+		nanos.sort_unstable();
+
+		let mid = nanos.len() / 2;
+		let median = match nanos.len() {
+			0 => return None,
+			len if len % 2 == 0 => (nanos[mid - 1] + nanos[mid]) / 2,
+			_ => nanos[mid],
+		};
+		Some(Stats {
+			median,
+			min: nanos[0],
+			max: nanos[nanos.len() - 1],
+		})
+	}
+}
+
+/// Timings of parsing and both parts of a day
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayBench {
+	pub year: u32,
+	pub day: u32,
+	pub iterations: u32,
+	pub parse: Stats,
+	pub part_1: Stats,
+	pub part_2: Stats,
+}
+
+impl DayBench {
+	fn steps(&self) -> [(&'static str, &Stats); 3] {
+		[
+			("parse", &self.parse),
+			("part 1", &self.part_1),
+			("part 2", &self.part_2),
+		]
+	}
+}
+
+#[derive(Debug, Error)]
+pub enum BaselineError {
+	#[error("failed to read {0}")]
+	Read(PathBuf, #[source] io::Error),
+	#[error("failed to write {0}")]
+	Write(PathBuf, #[source] io::Error),
+	#[error("failed to parse the baseline")]
+	Parse(#[from] serde_json::Error),
+}
+
+/// Results saved by `aqa_aoc bench --save`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+	pub days: Vec<DayBench>,
+}
+
+impl Baseline {
+	/// Loads the baseline, `None` when it wasn't saved yet
+	pub fn load(path: &Path) -> Result<Option<Baseline>, BaselineError> {
+		match fs::read_to_string(path) {
+			Ok(v) => Ok(Some(serde_json::from_str(&v)?)),
+			Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+			Err(e) => Err(BaselineError::Read(path.to_owned(), e)),
+		}
+	}
+
+	pub fn save(&self, path: &Path) -> Result<(), BaselineError> {
+		let json = serde_json::to_string_pretty(self)?;
+		fs::write(path, json + "\n").map_err(|e| BaselineError::Write(path.to_owned(), e))
+	}
+
+	pub fn get(&self, year: u32, day: u32) -> Option<&DayBench> {
+		self.days
+			.iter()
+			.find(|bench| bench.year == year && bench.day == day)
+	}
+
+	/// Replaces the results of the benchmarked days, keeping the other ones
+	pub fn update(&mut self, benches: &[DayBench]) {
+		for bench in benches {
+			match self
+				.days
+				.iter_mut()
+				.find(|old| old.year == bench.year && old.day == bench.day)
+			{
+				Some(old) => *old = bench.clone(),
+				None => self.days.push(bench.clone()),
+			}
+		}
+		self.days.sort_by_key(|bench| (bench.year, bench.day));
+	}
+}
+
+/// Step of a day whose median got slower than the baseline by more than the threshold
+#[derive(Debug, PartialEq)]
+pub struct Regression {
+	pub year: u32,
+	pub day: u32,
+	pub step: &'static str,
+	pub baseline: u64,
+	pub current: u64,
+}
+
+impl Regression {
+	/// Slowdown over the baseline in percent
+	pub fn slowdown(&self) -> f64 {
+		(self.current as f64 / self.baseline.max(1) as f64 - 1.0) * 100.0
+	}
+}
+
+/// Compares the medians against the baseline, `threshold` is in percent
+pub fn regressions(baseline: &Baseline, benches: &[DayBench], threshold: f64) -> Vec<Regression> {
+	benches
+		.iter()
+		.filter_map(|bench| Some((bench, baseline.get(bench.year, bench.day)?)))
+		.flat_map(|(bench, old)| {
+			bench
+				.steps()
+				.iter()
+				.zip(old.steps().iter())
+				.map(|((step, current), (_, old))| Regression {
+					year: bench.year,
+					day: bench.day,
+					step,
+					baseline: old.median,
+					current: current.median,
+				})
+				.collect::<Vec<_>>()
+		})
+		.filter(|regression| regression.slowdown() > threshold)
+		.collect()
+}
+
+/// Renders the results as a table, with the change of each median when a baseline is given
+pub fn render(benches: &[DayBench], baseline: Option<&Baseline>) -> String {
+	let mut table = format!(
+		"{:>3}  {:6}  {:>10}  {:>10}  {:>10}{}\n",
+		"Day",
+		"Step",
+		"Median",
+		"Min",
+		"Max",
+		if baseline.is_some() { "  Change" } else { "" }
+	);
+
+	for bench in benches {
+		let old = baseline.and_then(|baseline| baseline.get(bench.year, bench.day));
+		for (idx, (step, stats)) in bench.steps().iter().enumerate() {
+			let day = if idx == 0 {
+				bench.day.to_string()
+			} else {
+				String::new()
+			};
+			let change = match old {
+				Some(old) => {
+					let old = old.steps()[idx].1.median.max(1) as f64;
+					format!("  {:+.1}%", (stats.median as f64 / old - 1.0) * 100.0)
+				}
+				None => String::new(),
+			};
+			table.push_str(&format!(
+				"{:>3}  {:6}  {:>10}  {:>10}  {:>10}{}\n",
+				day,
+				step,
+				format_nanos(stats.median),
+				format_nanos(stats.min),
+				format_nanos(stats.max),
+				change
+			));
+		}
+	}
+
+	table
+}
+
+fn format_nanos(nanos: u64) -> String {
+	format!("{:.2?}", Duration::from_nanos(nanos))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{regressions, render, Baseline, DayBench, Stats};
+	use std::time::Duration;
+
+	fn stats(median: u64) -> Stats {
+		Stats {
+			median,
+			min: median / 2,
+			max: median * 2,
+		}
+	}
+
+	fn bench(day: u32, medians: [u64; 3]) -> DayBench {
+		DayBench {
+			year: 2020,
+			day,
+			iterations: 5,
+			parse: stats(medians[0]),
+			part_1: stats(medians[1]),
+			part_2: stats(medians[2]),
+		}
+	}
+
+	#[test]
+	fn stats_of_times() {
+		let times = [5, 1, 4, 2, 3].map(Duration::from_nanos);
+		assert_eq!(
+			Stats::from_times(&times),
+			Some(Stats {
+				median: 3,
+				min: 1,
+				max: 5
+			})
+		);
+		assert_eq!(Stats::from_times(&times[..4]).unwrap().median, 3);
+		assert_eq!(Stats::from_times(&[]), None);
+	}
+
+	#[test]
+	fn flags_regressions_above_threshold() {
+		let baseline = Baseline {
+			days: vec![bench(1, [100, 1000, 1000]), bench(2, [100, 100, 100])],
+		};
+		let current = [bench(1, [105, 1200, 900]), bench(3, [1, 1, 1])];
+
+		let found = regressions(&baseline, &current, 10.0);
+		assert_eq!(found.len(), 1);
+		assert_eq!((found[0].day, found[0].step), (1, "part 1"));
+		assert!((found[0].slowdown() - 20.0).abs() < 1e-9);
+
+		assert_eq!(regressions(&baseline, &current, 25.0), []);
+	}
+
+	#[test]
+	fn baseline_round_trip() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("bench_baseline.json");
+		assert!(Baseline::load(&path).unwrap().is_none());
+
+		let mut baseline = Baseline {
+			days: vec![bench(2, [1, 2, 3])],
+		};
+		baseline.update(&[bench(1, [4, 5, 6]), bench(2, [7, 8, 9])]);
+		baseline.save(&path).unwrap();
+
+		let loaded = Baseline::load(&path).unwrap().unwrap();
+		assert_eq!(loaded.days, [bench(1, [4, 5, 6]), bench(2, [7, 8, 9])]);
+	}
+
+	#[test]
+	fn renders_changes() {
+		let baseline = Baseline {
+			days: vec![bench(1, [1000, 1000, 1000])],
+		};
+		let table = render(&[bench(1, [1000, 1500, 500])], Some(&baseline));
+		let lines = table.lines().collect::<Vec<_>>();
+		assert_eq!(lines.len(), 4);
+		assert!(lines[0].ends_with("Change"));
+		assert!(lines[1].starts_with("  1  parse") && lines[1].ends_with("+0.0%"));
+		assert!(lines[2].ends_with("+50.0%"));
+		assert!(lines[3].ends_with("-50.0%"));
+
+		assert!(!render(&[bench(1, [1, 1, 1])], None).contains("Change"));
+	}
+}
@@ -0,0 +1,245 @@
+use sha2::{Digest, Sha256};
+use std::fs::{self, OpenOptions};
+use std::io::{self, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Minimal time between two requests sent to the site
+pub const DEFAULT_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long a private leaderboard is reused, the site asks not to poll it more often
+pub const LEADERBOARD_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// Returns `$XDG_CACHE_HOME/aqa_aoc` (or the platform equivalent)
+pub fn default_cache_dir() -> Option<PathBuf> {
+	dirs::cache_dir().map(|dir| dir.join("aqa_aoc"))
+}
+
+/// Hex encoded SHA-256 of the given bytes
+pub fn sha256_hex(data: &[u8]) -> String {
+	Sha256::digest(data)
+		.iter()
+		.map(|b| format!("{:02x}", b))
+		.collect()
+}
+
+/// Persistent cache of puzzle inputs, keyed by year, day and session cookie
+pub struct InputCache {
+	dir: PathBuf,
+	cookie_hash: String,
+}
+
+impl InputCache {
+	pub fn new(cache_dir: &Path, cookie: &str) -> InputCache {
+		InputCache {
+			dir: cache_dir.join("inputs"),
+			// Inputs differ by user, a shortened hash is enough to tell accounts apart
+			cookie_hash: sha256_hex(cookie.as_bytes())[..16].to_string(),
+		}
+	}
+
+	fn input_path(&self, year: u32, day: u32) -> PathBuf {
+		self.dir
+			.join(year.to_string())
+			.join(format!("day_{}-{}.txt", day, self.cookie_hash))
+	}
+
+	pub fn get(&self, year: u32, day: u32) -> io::Result<Option<String>> {
+		match fs::read_to_string(self.input_path(year, day)) {
+			Ok(v) => Ok(Some(v)),
+			Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+			Err(e) => Err(e),
+		}
+	}
+
+	pub fn put(&self, year: u32, day: u32, input: &str) -> io::Result<()> {
+		let path = self.input_path(year, day);
+		// SAFETY: input_path always has a parent
+		fs::create_dir_all(path.parent().unwrap())?;
+		fs::write(path, input)
+	}
+}
+
+/// Private leaderboard JSON kept for a limited time, keyed by year, leaderboard id and
+/// session cookie
+pub struct LeaderboardCache {
+	dir: PathBuf,
+	cookie_hash: String,
+	ttl: Duration,
+}
+
+impl LeaderboardCache {
+	pub fn new(cache_dir: &Path, cookie: &str, ttl: Duration) -> LeaderboardCache {
+		LeaderboardCache {
+			dir: cache_dir.join("leaderboards"),
+			cookie_hash: sha256_hex(cookie.as_bytes())[..16].to_string(),
+			ttl,
+		}
+	}
+
+	fn leaderboard_path(&self, year: u32, id: u64) -> PathBuf {
+		self.dir
+			.join(year.to_string())
+			.join(format!("{}-{}.json", id, self.cookie_hash))
+	}
+
+	/// Returns the cached leaderboard unless it is older than the TTL
+	pub fn get(&self, year: u32, id: u64) -> io::Result<Option<String>> {
+		let path = self.leaderboard_path(year, id);
+		let modified = match fs::metadata(&path).and_then(|metadata| metadata.modified()) {
+			Ok(v) => v,
+			Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+			Err(e) => return Err(e),
+		};
+		let age = SystemTime::now()
+			.duration_since(modified)
+			.unwrap_or_default();
+		if age >= self.ttl {
+			return Ok(None);
+		}
+
+		match fs::read_to_string(path) {
+			Ok(v) => Ok(Some(v)),
+			Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+			Err(e) => Err(e),
+		}
+	}
+
+	pub fn put(&self, year: u32, id: u64, json: &str) -> io::Result<()> {
+		let path = self.leaderboard_path(year, id);
+		// SAFETY: leaderboard_path always has a parent
+		fs::create_dir_all(path.parent().unwrap())?;
+		fs::write(path, json)
+	}
+}
+
+/// Spaces out requests, the time of the last request is kept on disk so it's respected
+/// across runs
+pub struct Throttle {
+	state_path: PathBuf,
+	interval: Duration,
+}
+
+impl Throttle {
+	pub fn new(cache_dir: &Path, interval: Duration) -> Throttle {
+		Throttle {
+			state_path: cache_dir.join("last_request"),
+			interval,
+		}
+	}
+
+	/// Blocks until a request can be sent and records it as sent.
+	///
+	/// The state file stays locked meanwhile, so concurrent runs take turns.
+	pub fn wait(&self) -> io::Result<()> {
+		if let Some(parent) = self.state_path.parent() {
+			fs::create_dir_all(parent)?;
+		}
+		let mut state = OpenOptions::new()
+			.read(true)
+			.write(true)
+			.create(true)
+			.truncate(false)
+			.open(&self.state_path)?;
+		state.lock()?;
+
+		let mut last_request = String::new();
+		state.read_to_string(&mut last_request)?;
+		if let Some(last_request) = parse_last_request(&last_request) {
+			let elapsed = SystemTime::now()
+				.duration_since(last_request)
+				.unwrap_or_default();
+			if elapsed < self.interval {
+				let remaining = self.interval - elapsed;
+				println!("Throttling, waiting {:.1}s", remaining.as_secs_f32());
+				thread::sleep(remaining);
+			}
+		}
+
+		let now = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.unwrap_or_default();
+		// Rounded up, a truncated time would let the next request go out slightly early
+		let millis = now.as_nanos().div_ceil(1_000_000);
+		state.set_len(0)?;
+		state.seek(SeekFrom::Start(0))?;
+		state.write_all(millis.to_string().as_bytes())
+	}
+}
+
+/// Time of the last request, stored as milliseconds since the epoch
+fn parse_last_request(state: &str) -> Option<SystemTime> {
+	state
+		.trim()
+		.parse::<u64>()
+		.ok()
+		.map(|millis| UNIX_EPOCH + Duration::from_millis(millis))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{InputCache, LeaderboardCache, Throttle};
+	use std::thread;
+	use std::time::{Duration, Instant};
+
+	#[test]
+	fn cache_is_keyed_by_cookie() {
+		let dir = tempfile::tempdir().unwrap();
+
+		let cache = InputCache::new(dir.path(), "cookie_a");
+		assert_eq!(cache.get(2020, 1).unwrap(), None);
+		cache.put(2020, 1, "1721\n").unwrap();
+		assert_eq!(cache.get(2020, 1).unwrap().as_deref(), Some("1721\n"));
+		assert_eq!(cache.get(2020, 2).unwrap(), None);
+		assert_eq!(cache.get(2019, 1).unwrap(), None);
+
+		let other_cache = InputCache::new(dir.path(), "cookie_b");
+		assert_eq!(other_cache.get(2020, 1).unwrap(), None);
+	}
+
+	#[test]
+	fn leaderboard_expires() {
+		let dir = tempfile::tempdir().unwrap();
+
+		let cache = LeaderboardCache::new(dir.path(), "cookie", Duration::from_secs(60));
+		assert_eq!(cache.get(2020, 1).unwrap(), None);
+		cache.put(2020, 1, "{}").unwrap();
+		assert_eq!(cache.get(2020, 1).unwrap().as_deref(), Some("{}"));
+		assert_eq!(cache.get(2020, 2).unwrap(), None);
+
+		let expired = LeaderboardCache::new(dir.path(), "cookie", Duration::from_secs(0));
+		assert_eq!(expired.get(2020, 1).unwrap(), None);
+	}
+
+	#[test]
+	fn throttle_persists_between_instances() {
+		let dir = tempfile::tempdir().unwrap();
+		let interval = Duration::from_millis(300);
+
+		let start = Instant::now();
+		Throttle::new(dir.path(), interval).wait().unwrap();
+		assert!(start.elapsed() < interval);
+
+		Throttle::new(dir.path(), interval).wait().unwrap();
+		assert!(start.elapsed() >= interval);
+	}
+
+	#[test]
+	fn concurrent_throttles_take_turns() {
+		let dir = tempfile::tempdir().unwrap();
+		let interval = Duration::from_millis(200);
+
+		let start = Instant::now();
+		let threads = (0..3)
+			.map(|_| {
+				let dir = dir.path().to_owned();
+				thread::spawn(move || Throttle::new(&dir, interval).wait().unwrap())
+			})
+			.collect::<Vec<_>>();
+		for thread in threads {
+			thread.join().unwrap();
+		}
+		assert!(start.elapsed() >= 2 * interval);
+	}
+}
@@ -0,0 +1,188 @@
+use crate::git::GitCfg;
+use crate::profile::Profile;
+use anyhow::Context;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub static AOC_ADDR: &str = "https://adventofcode.com";
+
+/// Environment variable overriding `base_url` from aoc_cfg.toml
+pub static AOC_BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+/// Environment variable with the session cookie, takes precedence over every other source
+pub static AOC_SESSION_ENV: &str = "AOC_SESSION";
+
+/// Returns the file the session cookie is read from when it's not set anywhere else,
+/// `$XDG_CONFIG_HOME/aqa_aoc/session` (or the platform equivalent)
+pub fn default_cookie_file() -> Option<PathBuf> {
+	dirs::config_dir().map(|dir| dir.join("aqa_aoc").join("session"))
+}
+
+/// Where the session cookie was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CookieSource {
+	Env,
+	ConfigFile,
+	CookieFile(PathBuf),
+}
+
+impl fmt::Display for CookieSource {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			CookieSource::Env => write!(f, "the {} environment variable", AOC_SESSION_ENV),
+			CookieSource::ConfigFile => write!(f, "session_cookie in the config file"),
+			CookieSource::CookieFile(path) => write!(f, "{}", path.display()),
+		}
+	}
+}
+
+#[derive(Deserialize)]
+pub struct AocCfg {
+	pub year: u32,
+	/// Prefer [AOC_SESSION_ENV] or the cookie file to keeping the cookie in plain text here
+	pub session_cookie: Option<String>,
+	/// Address of the AoC site, [AOC_ADDR] when not set
+	pub base_url: Option<String>,
+	/// Minimal number of seconds between two requests sent to the site
+	pub request_interval_secs: Option<u64>,
+	/// Directory with templates overriding the built-in day crate templates
+	pub template_dir: Option<PathBuf>,
+	/// Named sets of dependencies added to new day crates, see [crate::profile]
+	#[serde(default)]
+	pub profiles: BTreeMap<String, Profile>,
+	/// Commits new days and accepted answers when set
+	pub git: Option<GitCfg>,
+}
+
+impl AocCfg {
+	pub fn load(path: &Path) -> anyhow::Result<AocCfg> {
+		toml::from_slice(
+			&std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?,
+		)
+		.with_context(|| format!("failed to parse {}", path.display()))
+	}
+
+	/// Returns the site address, `AOC_BASE_URL` takes precedence over the config file
+	pub fn base_url(&self) -> String {
+		let base_url = match std::env::var(AOC_BASE_URL_ENV) {
+			Ok(v) if !v.is_empty() => v,
+			_ => self.base_url.as_deref().unwrap_or(AOC_ADDR).to_string(),
+		};
+		base_url.trim_end_matches('/').to_string()
+	}
+
+	/// Returns the session cookie, looked up in order in:
+	///
+	/// 1. the `AOC_SESSION` environment variable,
+	/// 2. `session_cookie` in the config file,
+	/// 3. the cookie file, see [default_cookie_file].
+	pub fn session_cookie(&self) -> anyhow::Result<(String, CookieSource)> {
+		let cookie_file = default_cookie_file();
+		resolve_session_cookie(
+			std::env::var(AOC_SESSION_ENV).ok(),
+			self.session_cookie.as_deref(),
+			cookie_file.as_deref(),
+		)?
+		.with_context(|| {
+			format!(
+				"no session cookie, set {}, session_cookie in the config file or write it to {}",
+				AOC_SESSION_ENV,
+				cookie_file
+					.as_deref()
+					.unwrap_or_else(|| Path::new("the cookie file"))
+					.display()
+			)
+		})
+	}
+
+	pub fn request_interval(&self) -> Duration {
+		self.request_interval_secs
+			.map(Duration::from_secs)
+			.unwrap_or(crate::cache::DEFAULT_REQUEST_INTERVAL)
+	}
+}
+
+fn resolve_session_cookie(
+	env: Option<String>,
+	cfg: Option<&str>,
+	cookie_file: Option<&Path>,
+) -> anyhow::Result<Option<(String, CookieSource)>> {
+	if let Some(cookie) = env.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+		return Ok(Some((cookie.to_string(), CookieSource::Env)));
+	}
+	if let Some(cookie) = cfg.map(str::trim).filter(|v| !v.is_empty()) {
+		return Ok(Some((cookie.to_string(), CookieSource::ConfigFile)));
+	}
+
+	let cookie_file = match cookie_file {
+		Some(v) => v,
+		None => return Ok(None),
+	};
+	let cookie = match std::fs::read_to_string(cookie_file) {
+		Ok(v) => v,
+		Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+		Err(e) => {
+			return Err(e).with_context(|| format!("failed to read {}", cookie_file.display()))
+		}
+	};
+	// A copy from the browser may include the name of the cookie
+	let cookie = cookie.trim();
+	let cookie = cookie.strip_prefix("session=").unwrap_or(cookie);
+
+	Ok(Some((
+		cookie.to_string(),
+		CookieSource::CookieFile(cookie_file.to_owned()),
+	))
+	.filter(|(cookie, _)| !cookie.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{resolve_session_cookie, CookieSource};
+
+	#[test]
+	fn session_cookie_precedence() {
+		let dir = tempfile::tempdir().unwrap();
+		let cookie_file = dir.path().join("session");
+		std::fs::write(&cookie_file, "session=from_file\n").unwrap();
+
+		let resolve = |env: Option<&str>, cfg| {
+			resolve_session_cookie(env.map(ToOwned::to_owned), cfg, Some(&cookie_file))
+				.unwrap()
+				.unwrap()
+		};
+		assert_eq!(
+			resolve(Some("from_env"), Some("from_cfg")),
+			("from_env".to_string(), CookieSource::Env)
+		);
+		assert_eq!(
+			resolve(Some(""), Some("from_cfg")),
+			("from_cfg".to_string(), CookieSource::ConfigFile)
+		);
+		assert_eq!(
+			resolve(None, None),
+			(
+				"from_file".to_string(),
+				CookieSource::CookieFile(cookie_file.clone())
+			)
+		);
+	}
+
+	#[test]
+	fn no_session_cookie() {
+		let dir = tempfile::tempdir().unwrap();
+		let cookie_file = dir.path().join("session");
+		assert!(resolve_session_cookie(None, None, Some(&cookie_file))
+			.unwrap()
+			.is_none());
+
+		std::fs::write(&cookie_file, "\n").unwrap();
+		assert!(resolve_session_cookie(None, Some(" "), Some(&cookie_file))
+			.unwrap()
+			.is_none());
+	}
+}
@@ -0,0 +1,232 @@
+use aqa_aoc::bench;
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+
+const AFTER_HELP: &str = "The session cookie is read from, in order:
+  1. the AOC_SESSION environment variable,
+  2. session_cookie in the config file,
+  3. the aqa_aoc/session file in the user config directory (~/.config on Linux).";
+
+/// Advent of Code workspace helper, without a subcommand it works like `fetch`
+#[derive(Debug, Parser)]
+#[command(
+	name = "aqa_aoc",
+	version,
+	args_conflicts_with_subcommands = true,
+	after_help = AFTER_HELP
+)]
+pub struct Cli {
+	/// Event to work with, the year from the config file when not set
+	#[arg(long, global = true)]
+	pub year: Option<u32>,
+	/// Config file to use
+	#[arg(long, global = true, default_value = "aoc_cfg.toml")]
+	pub config: PathBuf,
+	#[command(flatten)]
+	pub fetch: FetchArgs,
+	#[command(subcommand)]
+	pub command: Option<Command>,
+}
+
+#[derive(Debug, Args)]
+pub struct FetchArgs {
+	/// Day to work with, the first day without an input or the next new day when not set
+	#[arg(long, value_parser = clap::value_parser!(u32).range(1..=25))]
+	pub day: Option<u32>,
+	/// Download the input again, skipping the cache, and overwrite an existing input.txt
+	#[arg(long)]
+	pub force: bool,
+	/// Re-download the puzzle description and examples of an existing day
+	#[arg(long, requires = "day")]
+	pub refresh: bool,
+	/// Wait until the day unlocks (the next one when --day is not set), then fetch it
+	#[arg(long, conflicts_with = "refresh")]
+	pub wait: bool,
+	/// Print what would be created and written without changing anything
+	#[arg(long, conflicts_with_all = ["refresh", "wait"])]
+	pub dry_run: bool,
+	/// Download the input of every unlocked day of the year, creating missing crates
+	#[arg(long, conflicts_with_all = ["day", "refresh", "wait", "dry_run"])]
+	pub all: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+	/// Downloads the input of a day, creating its crate first if needed
+	Fetch(FetchArgs),
+	/// Creates the crate of a day and downloads its description, without the input
+	New {
+		/// Day to create, the next new day when not set
+		#[arg(long, value_parser = clap::value_parser!(u32).range(1..=25))]
+		day: Option<u32>,
+		/// Print what would be created without changing anything
+		#[arg(long)]
+		dry_run: bool,
+		/// Dependency profiles from aoc_cfg.toml to add, `default` when not set
+		#[arg(long, value_delimiter = ',')]
+		profile: Vec<String>,
+	},
+	/// Submits an answer
+	Submit {
+		#[arg(long, value_parser = clap::value_parser!(u32).range(1..=25))]
+		day: u32,
+		/// Part the answer is for, 1 or 2
+		#[arg(long, value_parser = clap::value_parser!(u32).range(1..=2))]
+		part: u32,
+		answer: String,
+		/// Send the answer even when the submission history rules it out
+		#[arg(long)]
+		force: bool,
+	},
+	/// Lists the answers submitted for a day with their verdicts
+	History {
+		/// Day to show, every day of the year when not set
+		#[arg(long, value_parser = clap::value_parser!(u32).range(1..=25))]
+		day: Option<u32>,
+	},
+	/// Runs the solution of a day, through the aoc_runner crate
+	Run {
+		#[arg(long, value_parser = clap::value_parser!(u32).range(1..=25))]
+		day: u32,
+		/// Run only this part
+		#[arg(long, value_parser = clap::value_parser!(u32).range(1..=2))]
+		part: Option<u32>,
+	},
+	/// Rebuilds a day, runs its tests and then the solution whenever its sources or inputs
+	/// change
+	Watch {
+		#[arg(long, value_parser = clap::value_parser!(u32).range(1..=25))]
+		day: u32,
+	},
+	/// Times parsing and both parts of every day against its input
+	Bench {
+		/// Benchmark only this day
+		#[arg(long, value_parser = clap::value_parser!(u32).range(1..=25))]
+		day: Option<u32>,
+		/// Runs of every day
+		#[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+		iterations: u32,
+		/// Slowdown of a median over the baseline reported as a regression, in percent
+		#[arg(long, default_value_t = bench::DEFAULT_THRESHOLD)]
+		threshold: f64,
+		/// Save the results as the new baseline
+		#[arg(long)]
+		save: bool,
+	},
+	/// Shows the state of every day of the year
+	Status {
+		/// Don't fetch the stars from the calendar page
+		#[arg(long)]
+		offline: bool,
+	},
+	/// Opens the puzzle page in the browser
+	Open {
+		/// Day to open, the last day of the workspace when not set
+		#[arg(long, value_parser = clap::value_parser!(u32).range(1..=25))]
+		day: Option<u32>,
+	},
+	/// Shows a private leaderboard
+	Leaderboard {
+		/// Leaderboard id, the number in its URL
+		#[arg(long)]
+		id: u64,
+		/// Print the JSON returned by the site instead of the table
+		#[arg(long)]
+		json: bool,
+		/// Skip the leaderboard cache
+		#[arg(long)]
+		force: bool,
+	},
+	/// Checks the session cookie and shows the user it belongs to
+	Whoami,
+	/// Prints the completion script for a shell
+	Completions { shell: clap_complete::Shell },
+}
+
+impl Cli {
+	/// Returns the subcommand to run, bare `aqa_aoc` fetches
+	pub fn into_command(self) -> Command {
+		self.command.unwrap_or(Command::Fetch(self.fetch))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Cli, Command, FetchArgs};
+	use clap::{CommandFactory, Parser};
+
+	#[test]
+	fn definition_is_valid() {
+		Cli::command().debug_assert();
+	}
+
+	#[test]
+	fn bare_invocation_fetches() {
+		let cli = Cli::try_parse_from(["aqa_aoc"]).unwrap();
+		assert!(matches!(
+			cli.into_command(),
+			Command::Fetch(FetchArgs {
+				day: None,
+				force: false,
+				refresh: false,
+				wait: false,
+				dry_run: false,
+				all: false
+			})
+		));
+
+		let cli = Cli::try_parse_from(["aqa_aoc", "--day=3", "--force"]).unwrap();
+		assert!(matches!(
+			cli.into_command(),
+			Command::Fetch(FetchArgs {
+				day: Some(3),
+				force: true,
+				..
+			})
+		));
+	}
+
+	#[test]
+	fn global_options() {
+		let cli = Cli::try_parse_from([
+			"aqa_aoc",
+			"run",
+			"--day=1",
+			"--year=2019",
+			"--config=a.toml",
+		])
+		.unwrap();
+		assert_eq!(cli.year, Some(2019));
+		assert_eq!(cli.config.to_str(), Some("a.toml"));
+		assert!(matches!(
+			cli.into_command(),
+			Command::Run { day: 1, part: None }
+		));
+	}
+
+	#[test]
+	fn profiles_are_comma_separated() {
+		let cli =
+			Cli::try_parse_from(["aqa_aoc", "new", "--profile=parsing,math", "--profile=grid"])
+				.unwrap();
+		match cli.into_command() {
+			Command::New { profile, .. } => assert_eq!(profile, ["parsing", "math", "grid"]),
+			command => panic!("unexpected command {:?}", command),
+		}
+	}
+
+	#[test]
+	fn invalid_arguments_are_rejected() {
+		assert!(Cli::try_parse_from(["aqa_aoc", "--dya=3"]).is_err());
+		assert!(Cli::try_parse_from(["aqa_aoc", "--refresh"]).is_err());
+		assert!(Cli::try_parse_from(["aqa_aoc", "submit", "--day=1", "--part=3", "42"]).is_err());
+		assert!(Cli::try_parse_from(["aqa_aoc", "--day=1", "run", "--day=1"]).is_err());
+		assert!(Cli::try_parse_from(["aqa_aoc", "fetch", "--id=1"]).is_err());
+		assert!(Cli::try_parse_from(["aqa_aoc", "--day=1", "--refresh", "--wait"]).is_err());
+		assert!(Cli::try_parse_from(["aqa_aoc", "--wait", "--dry-run"]).is_err());
+		assert!(Cli::try_parse_from(["aqa_aoc", "fetch", "--all", "--day=1"]).is_err());
+		assert!(Cli::try_parse_from(["aqa_aoc", "--day=0"]).is_err());
+		assert!(Cli::try_parse_from(["aqa_aoc", "new", "--day=26"]).is_err());
+		assert!(Cli::try_parse_from(["aqa_aoc", "run", "--day=40"]).is_err());
+	}
+}
@@ -0,0 +1,237 @@
+use crate::cache::Throttle;
+use crate::submit::{SubmissionParseError, SubmissionResult};
+use crate::unlock::{self, Backoff, Clock};
+use std::cell::Cell;
+use thiserror::Error;
+use ureq::Cookie;
+
+/// Status of an input request when the site doesn't accept the session cookie
+const REJECTED_COOKIE_STATUS: u16 = 500;
+
+#[derive(Debug, Error)]
+pub enum AocError {
+	#[error("invalid base url \"{0}\"")]
+	InvalidBaseUrl(String),
+	#[error(transparent)]
+	Synthetic(#[from] ureq::Error),
+	#[error("not logged in, the session cookie is missing or expired: {0}")]
+	NotLoggedIn(String),
+	#[error("{0} not found, the day might not be unlocked yet")]
+	NotFound(String),
+	#[error("no access to private leaderboard {0}, check the id and the session cookie")]
+	LeaderboardNotAccessible(u64),
+	#[error("failed to {action}: {status_line}")]
+	Status {
+		action: String,
+		status: u16,
+		status_line: String,
+	},
+	#[error("failed to read the response")]
+	Io(#[from] std::io::Error),
+	#[error("failed to update the request throttle")]
+	Throttle(#[source] std::io::Error),
+	#[error(transparent)]
+	UnexpectedResponse(#[from] SubmissionParseError),
+}
+
+/// HTTP client for the AoC site, sends the session cookie with every request
+pub struct AocClient {
+	addr: String,
+	agent: ureq::Agent,
+	throttle: Option<Throttle>,
+	unlock_retry: Option<(Backoff, Box<dyn Clock>)>,
+	/// Whether the site recognized the session cookie, asked once after a failed input request
+	cookie_accepted: Cell<Option<bool>>,
+}
+
+impl AocClient {
+	pub fn new(addr: &str, cookie: &str) -> Result<AocClient, AocError> {
+		let url = url::Url::parse(addr).map_err(|_| AocError::InvalidBaseUrl(addr.to_string()))?;
+		let domain = url
+			.host_str()
+			.ok_or_else(|| AocError::InvalidBaseUrl(addr.to_string()))?;
+
+		let cookie = Cookie::build("session", cookie)
+			.domain(domain)
+			.path("/")
+			.secure(url.scheme() == "https")
+			.finish()
+			.into_owned();
+
+		let agent = ureq::agent();
+		agent.set_cookie(cookie);
+
+		Ok(AocClient {
+			addr: addr.trim_end_matches('/').to_string(),
+			agent,
+			throttle: None,
+			unlock_retry: None,
+			cookie_accepted: Cell::new(None),
+		})
+	}
+
+	/// Makes every request wait for the throttle first
+	pub fn with_throttle(mut self, throttle: Throttle) -> AocClient {
+		self.throttle = Some(throttle);
+		self
+	}
+
+	/// Retries fetching puzzles and inputs that are not found yet, for use right after the unlock.
+	/// `clock` sleeps between the attempts.
+	pub fn with_unlock_retry(mut self, backoff: Backoff, clock: Box<dyn Clock>) -> AocClient {
+		self.unlock_retry = Some((backoff, clock));
+		self
+	}
+
+	fn throttle(&self) -> Result<(), AocError> {
+		match &self.throttle {
+			Some(throttle) => throttle.wait().map_err(AocError::Throttle),
+			None => Ok(()),
+		}
+	}
+
+	pub fn addr(&self) -> &str {
+		&self.addr
+	}
+
+	pub fn get_input(&self, year: u32, day: u32) -> Result<String, AocError> {
+		let req_adr = format!(
+			"{addr}/{year}/day/{day}/input",
+			addr = self.addr,
+			year = year,
+			day = day
+		);
+		let input = self.get_unlocked(&req_adr, "fetch input");
+		// An expired cookie makes the site fail with a server error instead of asking to log in
+		if let Err(AocError::Status {
+			status: REJECTED_COOKIE_STATUS,
+			..
+		}) = input
+		{
+			if !self.cookie_accepted(year) {
+				return Err(AocError::NotLoggedIn(
+					"the site rejected the session cookie".to_string(),
+				));
+			}
+		}
+		input
+	}
+
+	/// Asks the site whether it recognizes the session cookie, only the first time.
+	/// The cookie counts as accepted when the site can't tell.
+	fn cookie_accepted(&self, year: u32) -> bool {
+		if let Some(accepted) = self.cookie_accepted.get() {
+			return accepted;
+		}
+		let accepted = !matches!(self.whoami(year), Ok(None));
+		self.cookie_accepted.set(Some(accepted));
+		accepted
+	}
+
+	/// Returns the name of the user the session cookie belongs to, `None` when the site
+	/// doesn't recognize the cookie
+	pub fn whoami(&self, year: u32) -> Result<Option<String>, AocError> {
+		let page = self.get_event_page(year)?;
+		Ok(crate::event::logged_in_user(&page))
+	}
+
+	/// Returns the calendar page of an event
+	pub fn get_event_page(&self, year: u32) -> Result<String, AocError> {
+		let req_adr = format!("{addr}/{year}", addr = self.addr, year = year);
+		self.throttle()?;
+		println!("Request addr: {}", req_adr);
+		let response = self.agent.get(&req_adr).call();
+
+		response_body(response, "fetch event page")
+	}
+
+	pub fn get_puzzle_page(&self, year: u32, day: u32) -> Result<String, AocError> {
+		let req_adr = format!(
+			"{addr}/{year}/day/{day}",
+			addr = self.addr,
+			year = year,
+			day = day
+		);
+		self.get_unlocked(&req_adr, "fetch puzzle")
+	}
+
+	/// Sends a GET request for a page that exists only once its day is unlocked
+	fn get_unlocked(&self, req_adr: &str, action: &str) -> Result<String, AocError> {
+		let request = || {
+			self.throttle()?;
+			println!("Request addr: {}", req_adr);
+			response_body(self.agent.get(req_adr).call(), action)
+		};
+
+		match &self.unlock_retry {
+			Some((backoff, clock)) => unlock::retry_not_found(clock.as_ref(), backoff, request),
+			None => request(),
+		}
+	}
+
+	/// Returns the raw JSON of a private leaderboard
+	pub fn get_private_leaderboard(&self, year: u32, id: u64) -> Result<String, AocError> {
+		let req_adr = format!(
+			"{addr}/{year}/leaderboard/private/view/{id}.json",
+			addr = self.addr,
+			year = year,
+			id = id
+		);
+		self.throttle()?;
+		println!("Request addr: {}", req_adr);
+		let response = self.agent.get(&req_adr).call();
+
+		let body = response_body(response, "fetch leaderboard")?;
+		// Without access the site redirects to a regular page instead of failing
+		if !body.trim_start().starts_with('{') {
+			return Err(AocError::LeaderboardNotAccessible(id));
+		}
+		Ok(body)
+	}
+
+	pub fn submit_answer(
+		&self,
+		year: u32,
+		day: u32,
+		part: u32,
+		answer: &str,
+	) -> Result<SubmissionResult, AocError> {
+		let req_adr = format!(
+			"{addr}/{year}/day/{day}/answer",
+			addr = self.addr,
+			year = year,
+			day = day
+		);
+		self.throttle()?;
+		println!("Request addr: {}", req_adr);
+		let level = part.to_string();
+		let response = self
+			.agent
+			.post(&req_adr)
+			.send_form(&[("level", &level), ("answer", answer)]);
+
+		let body = response_body(response, "submit answer")?;
+		SubmissionResult::from_html(&body).map_err(Into::into)
+	}
+}
+
+fn response_body(response: ureq::Response, action: &str) -> Result<String, AocError> {
+	if response.synthetic() {
+		// SAFETY: synthetic returns true only when error is Some
+		return Err(response.into_synthetic_error().unwrap().into());
+	}
+
+	match response.status() {
+		400 => {
+			let body = response.into_string()?;
+			Err(AocError::NotLoggedIn(body.trim().to_string()))
+		}
+		404 => Err(AocError::NotFound(response.get_url().to_string())),
+		_ if response.error() => Err(AocError::Status {
+			action: action.to_string(),
+			status: response.status(),
+			status_line: response.status_line().to_string(),
+		}),
+		_ => response.into_string().map_err(Into::into),
+	}
+}
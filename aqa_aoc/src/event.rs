@@ -0,0 +1,86 @@
+use scraper::{Html, Node, Selector};
+use std::collections::BTreeMap;
+
+/// Returns the name of the logged in user shown in the header of an event page (`/{year}`),
+/// `None` when the page was served to a visitor that is not logged in
+pub fn logged_in_user(html: &str) -> Option<String> {
+	let document = Html::parse_document(html);
+	// SAFETY: the selector is a valid constant
+	let selector = Selector::parse("header div.user").unwrap();
+
+	let user = document.select(&selector).next()?;
+	// The star count and the AoC++ badge are child elements, the name is the only text
+	let name = user
+		.children()
+		.filter_map(|child| match child.value() {
+			Node::Text(text) => Some(&**text),
+			_ => None,
+		})
+		.collect::<String>();
+
+	Some(name.trim().to_string()).filter(|name| !name.is_empty())
+}
+
+/// Returns the number of stars of every unlocked day shown on the calendar of an event page
+pub fn stars(html: &str) -> BTreeMap<u32, u32> {
+	let document = Html::parse_document(html);
+	// SAFETY: the selector is a valid constant
+	let selector = Selector::parse("pre.calendar a").unwrap();
+
+	document
+		.select(&selector)
+		.filter_map(|day| {
+			let classes = day.value().classes().collect::<Vec<_>>();
+			let day = classes
+				.iter()
+				.find_map(|class| class.strip_prefix("calendar-day"))
+				.and_then(|day| day.parse::<u32>().ok())?;
+			let stars = if classes.contains(&"calendar-verycomplete") {
+				2
+			} else if classes.contains(&"calendar-complete") {
+				1
+			} else {
+				0
+			};
+			Some((day, stars))
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{logged_in_user, stars};
+
+	#[test]
+	fn user_name() {
+		let page = r#"<html><body><header><div><h1 class="title-global"><a href="/">Advent of Code</a></h1><nav><ul><li><a href="/2020/about">[About]</a></li></ul></nav><div class="user">aQaTL <a href="/2020/support" class="supporter-badge" title="Advent of Code Supporter">AoC++</a> <span class="star-count">36*</span></div></div></header></body></html>"#;
+		assert_eq!(logged_in_user(page).as_deref(), Some("aQaTL"));
+
+		let page = r#"<header><div class="user">(anonymous user #123456) <span class="star-count">2*</span></div></header>"#;
+		assert_eq!(
+			logged_in_user(page).as_deref(),
+			Some("(anonymous user #123456)")
+		);
+	}
+
+	#[test]
+	fn not_logged_in() {
+		let page = r#"<html><body><header><div><nav><ul><li><a href="/2020/auth/login">[Log In]</a></li></ul></nav></div></header></body></html>"#;
+		assert_eq!(logged_in_user(page), None);
+	}
+
+	#[test]
+	fn calendar_stars() {
+		let page = r#"<main><pre class="calendar"><span aria-hidden="true" class="calendar-day25">                      25</span>
+<a aria-label="Day 3, one star" href="/2020/day/3" class="calendar-day3 calendar-complete">   <span class="calendar-day">3</span> <span class="calendar-mark-complete">*</span><span class="calendar-mark-verycomplete">*</span></a>
+<a aria-label="Day 2" href="/2020/day/2" class="calendar-day2">   <span class="calendar-day">2</span></a>
+<a aria-label="Day 1, two stars" href="/2020/day/1" class="calendar-day1 calendar-verycomplete">   <span class="calendar-day">1</span></a>
+</pre></main>"#;
+
+		assert_eq!(
+			stars(page).into_iter().collect::<Vec<_>>(),
+			[(1, 2), (2, 0), (3, 1)]
+		);
+		assert!(stars("<html></html>").is_empty());
+	}
+}
@@ -0,0 +1,342 @@
+use serde::Deserialize;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use thiserror::Error;
+
+const IGNORE_BLOCK_BEGIN: &str = "# BEGIN aqa_aoc managed block";
+const IGNORE_BLOCK_END: &str = "# END aqa_aoc managed block";
+
+/// Ignored by the managed block, AoC asks not to share the inputs
+const IGNORED: &[&str] = &["**/input.txt"];
+
+pub const IGNORE_MESSAGE: &str = "Ignore the puzzle inputs";
+pub const UNTRACK_MESSAGE: &str = "Stop tracking the puzzle inputs";
+
+/// `[git]` section of aoc_cfg.toml, the integration is enabled when it's present
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct GitCfg {
+	/// Create a `day-N` branch for every new day instead of committing on the current branch
+	#[serde(default)]
+	pub branch_per_day: bool,
+}
+
+#[derive(Debug, Error)]
+pub enum GitError {
+	#[error("failed to run git")]
+	Spawn(#[source] io::Error),
+	#[error("`git {command}` failed: {stderr}")]
+	Failed { command: String, stderr: String },
+	#[error("failed to update {0}")]
+	Gitignore(PathBuf, #[source] io::Error),
+}
+
+/// Repository the workspace is in, every command runs in `dir` with plain git
+pub struct Git {
+	dir: PathBuf,
+}
+
+impl Git {
+	pub fn new(dir: &Path) -> Git {
+		Git {
+			dir: dir.to_owned(),
+		}
+	}
+
+	fn run(&self, args: &[&str]) -> Result<String, GitError> {
+		self.run_with_index(None, args)
+	}
+
+	/// Runs git with `GIT_INDEX_FILE` set to `index`, or with the repository's own index
+	fn run_with_index(&self, index: Option<&Path>, args: &[&str]) -> Result<String, GitError> {
+		let mut command = Command::new("git");
+		command.arg("-C").arg(&self.dir).args(args);
+		if let Some(index) = index {
+			command.env("GIT_INDEX_FILE", index);
+		}
+		let output = command.output().map_err(GitError::Spawn)?;
+
+		if !output.status.success() {
+			return Err(GitError::Failed {
+				command: args.join(" "),
+				stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+			});
+		}
+		Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+	}
+
+	/// Whether `.gitignore` lacks the managed block or has an outdated one
+	pub fn gitignore_outdated(&self) -> Result<bool, GitError> {
+		let (gitignore, updated) = self.read_gitignore()?;
+		Ok(gitignore != updated)
+	}
+
+	fn read_gitignore(&self) -> Result<(String, String), GitError> {
+		let path = self.dir.join(".gitignore");
+		let gitignore = match fs::read_to_string(&path) {
+			Ok(v) => v,
+			Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+			Err(e) => return Err(GitError::Gitignore(path, e)),
+		};
+		let updated = with_ignore_block(&gitignore);
+		Ok((gitignore, updated))
+	}
+
+	/// Inputs that are still in the index, committed before they were ignored
+	pub fn tracked_inputs(&self) -> Result<Vec<String>, GitError> {
+		let mut args = vec!["ls-files", "--"];
+		let pathspecs = IGNORED
+			.iter()
+			.map(|pattern| format!(":(glob){}", pattern))
+			.collect::<Vec<_>>();
+		args.extend(pathspecs.iter().map(String::as_str));
+		let files = self.run(&args)?;
+		Ok(files.lines().map(str::to_string).collect())
+	}
+
+	/// Adds the managed block to `.gitignore` and stops tracking the inputs committed before,
+	/// the files themselves are kept. Both go in a commit of their own, nothing else staged is
+	/// included.
+	///
+	/// Returns the untracked inputs, or `None` when everything was already in place.
+	pub fn untrack_inputs(&self) -> Result<Option<Vec<String>>, GitError> {
+		let (gitignore, updated) = self.read_gitignore()?;
+		let inputs = self.tracked_inputs()?;
+		if gitignore == updated && inputs.is_empty() {
+			return Ok(None);
+		}
+		if gitignore != updated {
+			let path = self.dir.join(".gitignore");
+			fs::write(&path, updated).map_err(|e| GitError::Gitignore(path, e))?;
+		}
+
+		let message = if inputs.is_empty() {
+			IGNORE_MESSAGE
+		} else {
+			UNTRACK_MESSAGE
+		};
+		let inputs_ref = inputs.iter().map(String::as_str).collect::<Vec<_>>();
+		self.commit_only(message, &[".gitignore"], &inputs_ref)?;
+		Ok(Some(inputs))
+	}
+
+	pub fn current_branch(&self) -> Result<String, GitError> {
+		self.run(&["rev-parse", "--abbrev-ref", "HEAD"])
+	}
+
+	/// Switches to `branch`, creating it from the current commit when it doesn't exist.
+	/// Uncommitted changes are carried over.
+	pub fn switch_branch(&self, branch: &str) -> Result<(), GitError> {
+		let exists = self
+			.run(&[
+				"rev-parse",
+				"--verify",
+				"--quiet",
+				&format!("refs/heads/{}", branch),
+			])
+			.is_ok();
+		if exists {
+			self.run(&["checkout", "-q", branch])?;
+		} else {
+			self.run(&["checkout", "-q", "-b", branch])?;
+		}
+		Ok(())
+	}
+
+	/// Commits the changes of `paths`, whatever else is staged stays staged and out of the
+	/// commit.
+	///
+	/// Paths that don't exist are skipped, returns `false` when there was nothing to commit.
+	pub fn commit(&self, paths: &[&str], message: &str) -> Result<bool, GitError> {
+		let paths = paths
+			.iter()
+			.copied()
+			.filter(|path| self.dir.join(path).exists())
+			.collect::<Vec<_>>();
+		if paths.is_empty() {
+			return Ok(false);
+		}
+		self.commit_only(message, &paths, &[])
+	}
+
+	/// Commits `add` and the removal of `remove` from a separate index that starts at HEAD,
+	/// then resets those paths in the repository's index to the new commit
+	fn commit_only(&self, message: &str, add: &[&str], remove: &[&str]) -> Result<bool, GitError> {
+		let index = self.run(&["rev-parse", "--git-path", "aqa_aoc.index"])?;
+		let index = self.dir.join(index);
+		let committed = self.commit_in_index(&index, message, add, remove);
+		let _ = fs::remove_file(&index);
+		let committed = committed?;
+
+		if committed {
+			let mut reset = vec!["reset", "-q", "--"];
+			reset.extend(add);
+			reset.extend(remove);
+			self.run(&reset)?;
+		}
+		Ok(committed)
+	}
+
+	fn commit_in_index(
+		&self,
+		index: &Path,
+		message: &str,
+		add: &[&str],
+		remove: &[&str],
+	) -> Result<bool, GitError> {
+		let git = |args: &[&str]| self.run_with_index(Some(index), args);
+
+		if self
+			.run(&["rev-parse", "--verify", "--quiet", "HEAD"])
+			.is_ok()
+		{
+			git(&["read-tree", "HEAD"])?;
+		} else {
+			git(&["read-tree", "--empty"])?;
+		}
+		if !add.is_empty() {
+			let mut args = vec!["add", "-A", "--"];
+			args.extend(add);
+			git(&args)?;
+		}
+		if !remove.is_empty() {
+			let mut args = vec!["rm", "-q", "--cached", "--ignore-unmatch", "--"];
+			args.extend(remove);
+			git(&args)?;
+		}
+
+		if git(&["diff", "--cached", "--quiet"]).is_ok() {
+			return Ok(false);
+		}
+		git(&["commit", "-q", "-m", message])?;
+		Ok(true)
+	}
+}
+
+/// Returns `gitignore` with the managed block added or replaced, the rest is kept as is
+pub fn with_ignore_block(gitignore: &str) -> String {
+	let block = format!(
+		"{}\n{}\n{}\n",
+		IGNORE_BLOCK_BEGIN,
+		IGNORED.join("\n"),
+		IGNORE_BLOCK_END
+	);
+
+	let begin = gitignore.find(IGNORE_BLOCK_BEGIN);
+	let end = begin.and_then(|begin| {
+		gitignore[begin..]
+			.find(IGNORE_BLOCK_END)
+			.map(|end| begin + end + IGNORE_BLOCK_END.len())
+	});
+	match (begin, end) {
+		(Some(begin), Some(end)) => {
+			let rest = gitignore[end..]
+				.strip_prefix('\n')
+				.unwrap_or(&gitignore[end..]);
+			format!("{}{}{}", &gitignore[..begin], block, rest)
+		}
+		_ if gitignore.is_empty() || gitignore.ends_with('\n') => format!("{}{}", gitignore, block),
+		_ => format!("{}\n{}", gitignore, block),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{with_ignore_block, Git};
+	use std::fs;
+	use std::path::Path;
+	use std::process::Command;
+
+	const BLOCK: &str =
+		"# BEGIN aqa_aoc managed block\n**/input.txt\n# END aqa_aoc managed block\n";
+
+	fn init_repo(dir: &Path) -> Git {
+		for args in [
+			&["init", "-q"][..],
+			&["config", "user.name", "aqa_aoc"],
+			&["config", "user.email", "aqa_aoc@localhost"],
+			&["config", "commit.gpgsign", "false"],
+		] {
+			let status = Command::new("git")
+				.arg("-C")
+				.arg(dir)
+				.args(args)
+				.status()
+				.unwrap();
+			assert!(status.success());
+		}
+		Git::new(dir)
+	}
+
+	#[test]
+	fn ignore_block_is_managed() {
+		assert_eq!(with_ignore_block(""), BLOCK);
+		assert_eq!(with_ignore_block("target/"), format!("target/\n{}", BLOCK));
+
+		let outdated = "target/\n# BEGIN aqa_aoc managed block\n*.txt\n# END aqa_aoc managed block\nCargo.lock\n";
+		let updated = with_ignore_block(outdated);
+		assert_eq!(updated, format!("target/\n{}Cargo.lock\n", BLOCK));
+		assert_eq!(with_ignore_block(&updated), updated);
+	}
+
+	#[test]
+	fn inputs_are_untracked() {
+		let dir = tempfile::tempdir().unwrap();
+		let git = init_repo(dir.path());
+		fs::create_dir(dir.path().join("day_1")).unwrap();
+		fs::write(dir.path().join("day_1").join("input.txt"), "1\n").unwrap();
+		fs::write(dir.path().join("day_1").join("main.rs"), "").unwrap();
+		git.run(&["add", "-A"]).unwrap();
+		git.run(&["commit", "-q", "-m", "Add day 1"]).unwrap();
+		fs::write(dir.path().join("notes.md"), "").unwrap();
+		git.run(&["add", "notes.md"]).unwrap();
+
+		assert!(git.gitignore_outdated().unwrap());
+		assert_eq!(git.tracked_inputs().unwrap(), ["day_1/input.txt"]);
+		assert_eq!(
+			git.untrack_inputs().unwrap(),
+			Some(vec!["day_1/input.txt".to_string()])
+		);
+		assert_eq!(git.untrack_inputs().unwrap(), None);
+
+		assert_eq!(
+			git.run(&["show", "--name-status", "--format=%s"]).unwrap(),
+			"Stop tracking the puzzle inputs\n\nA\t.gitignore\nD\tday_1/input.txt"
+		);
+		assert!(dir.path().join("day_1").join("input.txt").exists());
+		assert_eq!(git.run(&["status", "--porcelain"]).unwrap(), "A  notes.md");
+	}
+
+	#[test]
+	fn day_branch_and_commits() {
+		let dir = tempfile::tempdir().unwrap();
+		let git = init_repo(dir.path());
+		git.run(&["commit", "-q", "--allow-empty", "-m", "Initial commit"])
+			.unwrap();
+		assert_eq!(git.untrack_inputs().unwrap(), Some(vec![]));
+
+		fs::create_dir(dir.path().join("day_2")).unwrap();
+		fs::write(dir.path().join("day_2").join("input.txt"), "2\n").unwrap();
+		fs::write(dir.path().join("day_2").join("lib.rs"), "").unwrap();
+		fs::write(dir.path().join("notes.md"), "").unwrap();
+		git.run(&["add", "notes.md"]).unwrap();
+
+		git.switch_branch("day-2").unwrap();
+		assert_eq!(git.current_branch().unwrap(), "day-2");
+		assert!(git
+			.commit(&["day_2", ".gitignore", "answers.toml"], "Add day 2")
+			.unwrap());
+		assert!(!git.commit(&["day_2"], "Nothing changed").unwrap());
+
+		assert_eq!(
+			git.run(&["show", "--name-only", "--format=%s"]).unwrap(),
+			"Add day 2\n\nday_2/lib.rs"
+		);
+		assert_eq!(git.run(&["status", "--porcelain"]).unwrap(), "A  notes.md");
+
+		// Switching back to an existing branch doesn't recreate it
+		git.switch_branch("day-2").unwrap();
+		assert_eq!(git.run(&["rev-list", "--count", "HEAD"]).unwrap(), "3");
+	}
+}
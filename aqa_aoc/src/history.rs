@@ -0,0 +1,507 @@
+use crate::submit::SubmissionResult;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+/// Every answer submitted through aqa_aoc, kept at the workspace root
+pub const SUBMISSIONS_FILE: &str = "submissions.json";
+
+/// Wait after a wrong answer, in seconds, by how many wrong answers to the day came within
+/// [RECENT_WRONG_ANSWERS] of it. The site starts at a minute and makes you wait longer after
+/// repeated wrong answers, the last wait is kept for any further ones.
+const WRONG_ANSWER_COOLDOWNS: &[u64] = &[60, 60, 60, 300, 600, 900];
+
+/// Window in which wrong answers count towards a longer cooldown
+const RECENT_WRONG_ANSWERS: Duration = Duration::from_secs(3600);
+
+/// Wait after the `count`th recent wrong answer, counted from 1
+pub fn wrong_answer_cooldown(count: usize) -> Duration {
+	let index = count.clamp(1, WRONG_ANSWER_COOLDOWNS.len()) - 1;
+	Duration::from_secs(WRONG_ANSWER_COOLDOWNS[index])
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+	Correct,
+	Wrong,
+	TooHigh,
+	TooLow,
+	AlreadySolved,
+	RateLimited,
+}
+
+impl Verdict {
+	fn is_wrong(self) -> bool {
+		matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+	}
+}
+
+impl fmt::Display for Verdict {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Verdict::Correct => write!(f, "correct"),
+			Verdict::Wrong => write!(f, "wrong"),
+			Verdict::TooHigh => write!(f, "too high"),
+			Verdict::TooLow => write!(f, "too low"),
+			Verdict::AlreadySolved => write!(f, "already solved"),
+			Verdict::RateLimited => write!(f, "rate limited"),
+		}
+	}
+}
+
+/// Single answer sent to the site and what it said about it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+	pub year: u32,
+	pub day: u32,
+	pub part: u32,
+	pub answer: String,
+	pub verdict: Verdict,
+	/// Unix time of the submission, in seconds
+	pub time: u64,
+	/// Time left to wait reported with a [Verdict::RateLimited], in seconds
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub wait: Option<u64>,
+}
+
+impl Submission {
+	pub fn new(
+		year: u32,
+		day: u32,
+		part: u32,
+		answer: &str,
+		result: SubmissionResult,
+		time: SystemTime,
+	) -> Submission {
+		let (verdict, wait) = match result {
+			SubmissionResult::Correct => (Verdict::Correct, None),
+			SubmissionResult::Wrong => (Verdict::Wrong, None),
+			SubmissionResult::TooHigh => (Verdict::TooHigh, None),
+			SubmissionResult::TooLow => (Verdict::TooLow, None),
+			SubmissionResult::AlreadySolved => (Verdict::AlreadySolved, None),
+			SubmissionResult::RateLimited { wait } => (Verdict::RateLimited, Some(wait.as_secs())),
+		};
+		Submission {
+			year,
+			day,
+			part,
+			answer: answer.to_string(),
+			verdict,
+			time: unix_secs(time),
+			wait,
+		}
+	}
+
+	/// End of the wait the site imposes after this submission, `earlier` are the submissions
+	/// to the same day before it
+	fn cooldown_end(&self, earlier: &[&Submission]) -> Option<u64> {
+		match self.verdict {
+			Verdict::RateLimited => self.wait.map(|wait| self.time + wait),
+			verdict if verdict.is_wrong() => {
+				let since = self.time.saturating_sub(RECENT_WRONG_ANSWERS.as_secs());
+				let recent = earlier
+					.iter()
+					.filter(|s| s.verdict.is_wrong() && s.time >= since)
+					.count();
+				Some(self.time + wrong_answer_cooldown(recent + 1).as_secs())
+			}
+			_ => None,
+		}
+	}
+}
+
+/// Reason to not send an answer
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Refusal {
+	#[error("part {part} was already solved with {answer}")]
+	Solved { part: u32, answer: String },
+	#[error("the site said part {part} was already solved")]
+	AlreadySolved { part: u32 },
+	#[error("{answer} was already judged {verdict}")]
+	AlreadyWrong { answer: String, verdict: Verdict },
+	#[error("{answer} is not below {bound}, which was too high")]
+	TooHigh { answer: String, bound: String },
+	#[error("{answer} is not above {bound}, which was too low")]
+	TooLow { answer: String, bound: String },
+	#[error("the site asked to wait, {}s left", .remaining.as_secs())]
+	Cooldown { remaining: Duration },
+}
+
+#[derive(Debug, Error)]
+pub enum HistoryError {
+	#[error("failed to read {0}")]
+	Read(PathBuf, #[source] io::Error),
+	#[error("failed to write {0}")]
+	Write(PathBuf, #[source] io::Error),
+	#[error("failed to parse the submission history")]
+	Parse(#[from] serde_json::Error),
+}
+
+/// Submission history of the workspace, oldest first
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct History {
+	pub submissions: Vec<Submission>,
+}
+
+impl History {
+	/// Loads the history, a missing file is an empty history
+	pub fn load(path: &Path) -> Result<History, HistoryError> {
+		match fs::read_to_string(path) {
+			Ok(v) => Ok(serde_json::from_str(&v)?),
+			Err(e) if e.kind() == ErrorKind::NotFound => Ok(History::default()),
+			Err(e) => Err(HistoryError::Read(path.to_owned(), e)),
+		}
+	}
+
+	pub fn save(&self, path: &Path) -> Result<(), HistoryError> {
+		let json = serde_json::to_string_pretty(self)?;
+		fs::write(path, json + "\n").map_err(|e| HistoryError::Write(path.to_owned(), e))
+	}
+
+	pub fn record(&mut self, submission: Submission) {
+		self.submissions.push(submission);
+	}
+
+	/// Submissions of a day, or of every day of `year` when `day` is `None`
+	pub fn of_day(&self, year: u32, day: Option<u32>) -> impl Iterator<Item = &Submission> {
+		self.submissions.iter().filter(move |submission| {
+			submission.year == year && day.is_none_or(|day| submission.day == day)
+		})
+	}
+
+	/// Checks an answer against the earlier submissions of its part before it's sent.
+	///
+	/// Bounds from "too high" and "too low" verdicts only apply to integer answers.
+	pub fn check(
+		&self,
+		year: u32,
+		day: u32,
+		part: u32,
+		answer: &str,
+		now: SystemTime,
+	) -> Result<(), Refusal> {
+		let answer = answer.trim();
+		let same_part = self
+			.of_day(year, Some(day))
+			.filter(|submission| submission.part == part)
+			.collect::<Vec<_>>();
+
+		if let Some(solved) = same_part.iter().find(|s| s.verdict == Verdict::Correct) {
+			return Err(Refusal::Solved {
+				part,
+				answer: solved.answer.clone(),
+			});
+		}
+		if same_part
+			.iter()
+			.any(|s| s.verdict == Verdict::AlreadySolved)
+		{
+			return Err(Refusal::AlreadySolved { part });
+		}
+		if let Some(wrong) = same_part
+			.iter()
+			.find(|s| s.verdict.is_wrong() && s.answer.trim() == answer)
+		{
+			return Err(Refusal::AlreadyWrong {
+				answer: answer.to_string(),
+				verdict: wrong.verdict,
+			});
+		}
+
+		if let Ok(value) = answer.parse::<i128>() {
+			let bound = |verdict| {
+				same_part
+					.iter()
+					.filter(move |s| s.verdict == verdict)
+					.filter_map(|s| Some((s.answer.trim().parse::<i128>().ok()?, &s.answer)))
+			};
+			if let Some((high, bound)) = bound(Verdict::TooHigh).min() {
+				if value >= high {
+					return Err(Refusal::TooHigh {
+						answer: answer.to_string(),
+						bound: bound.clone(),
+					});
+				}
+			}
+			if let Some((low, bound)) = bound(Verdict::TooLow).max() {
+				if value <= low {
+					return Err(Refusal::TooLow {
+						answer: answer.to_string(),
+						bound: bound.clone(),
+					});
+				}
+			}
+		}
+
+		let now = unix_secs(now);
+		let same_day = self.of_day(year, Some(day)).collect::<Vec<_>>();
+		let cooldown_end = same_day
+			.iter()
+			.enumerate()
+			.filter_map(|(i, submission)| submission.cooldown_end(&same_day[..i]))
+			.max();
+		match cooldown_end {
+			Some(end) if end > now => Err(Refusal::Cooldown {
+				remaining: Duration::from_secs(end - now),
+			}),
+			_ => Ok(()),
+		}
+	}
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+	time.duration_since(UNIX_EPOCH)
+		.map(|since| since.as_secs())
+		.unwrap_or(0)
+}
+
+/// Formats unix time as `YYYY-MM-DD hh:mm:ss` in UTC
+pub fn format_utc(secs: u64) -> String {
+	// Inverse of days_from_civil in the unlock module, years start in March
+	let days = secs / 86400 + 719_468;
+	let era = days / 146_097;
+	let day_of_era = days % 146_097;
+	let year_of_era =
+		(day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let month = (5 * day_of_year + 2) / 153;
+	let day = day_of_year - (153 * month + 2) / 5 + 1;
+	let month = if month < 10 { month + 3 } else { month - 9 };
+	let year = era * 400 + year_of_era + u64::from(month <= 2);
+
+	format!(
+		"{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+		year,
+		month,
+		day,
+		secs % 86400 / 3600,
+		secs % 3600 / 60,
+		secs % 60
+	)
+}
+
+/// Renders the submissions as a table, times are in UTC
+pub fn render<'a>(submissions: impl IntoIterator<Item = &'a Submission>) -> String {
+	let rows = submissions
+		.into_iter()
+		.map(|submission| {
+			[
+				submission.day.to_string(),
+				submission.part.to_string(),
+				format_utc(submission.time),
+				submission.answer.clone(),
+				match submission.wait {
+					Some(wait) => format!("{}, {}s left to wait", submission.verdict, wait),
+					None => submission.verdict.to_string(),
+				},
+			]
+		})
+		.collect::<Vec<_>>();
+
+	let answer_width = rows
+		.iter()
+		.map(|row| row[3].len())
+		.chain(Some("Answer".len()))
+		.max()
+		.unwrap_or(0);
+	let mut table = format!(
+		"Day  Part  Time (UTC)           {:1$}  Verdict\n",
+		"Answer", answer_width
+	);
+	for row in rows {
+		table.push_str(&format!(
+			"{:>3}  {:>4}  {}  {:width$}  {}\n",
+			row[0],
+			row[1],
+			row[2],
+			row[3],
+			row[4],
+			width = answer_width
+		));
+	}
+
+	table
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{format_utc, render, wrong_answer_cooldown, History, Refusal, Submission, Verdict};
+	use crate::submit::SubmissionResult;
+	use std::time::{Duration, UNIX_EPOCH};
+
+	/// 2020-12-01T05:00:00Z
+	const UNLOCK: u64 = 1_606_798_800;
+
+	fn submission(part: u32, answer: &str, result: SubmissionResult, secs: u64) -> Submission {
+		Submission::new(
+			2020,
+			1,
+			part,
+			answer,
+			result,
+			UNIX_EPOCH + Duration::from_secs(UNLOCK + secs),
+		)
+	}
+
+	fn history() -> History {
+		History {
+			submissions: vec![
+				submission(1, "1000", SubmissionResult::TooHigh, 100),
+				submission(1, "100", SubmissionResult::TooLow, 200),
+				submission(1, "abc", SubmissionResult::Wrong, 300),
+				submission(1, "500", SubmissionResult::TooHigh, 400),
+			],
+		}
+	}
+
+	fn check(history: &History, part: u32, answer: &str, secs: u64) -> Result<(), Refusal> {
+		history.check(
+			2020,
+			1,
+			part,
+			answer,
+			UNIX_EPOCH + Duration::from_secs(UNLOCK + secs),
+		)
+	}
+
+	#[test]
+	fn refuses_known_wrong_answers() {
+		let history = history();
+		assert_eq!(
+			check(&history, 1, " abc ", 1000),
+			Err(Refusal::AlreadyWrong {
+				answer: "abc".to_string(),
+				verdict: Verdict::Wrong
+			})
+		);
+		assert_eq!(
+			check(&history, 1, "700", 1000),
+			Err(Refusal::TooHigh {
+				answer: "700".to_string(),
+				bound: "500".to_string()
+			})
+		);
+		assert_eq!(
+			check(&history, 1, "100", 1000),
+			Err(Refusal::AlreadyWrong {
+				answer: "100".to_string(),
+				verdict: Verdict::TooLow
+			})
+		);
+		assert!(matches!(
+			check(&history, 1, "-5", 1000),
+			Err(Refusal::TooLow { bound, .. }) if bound == "100"
+		));
+		assert_eq!(check(&history, 1, "499", 1000), Ok(()));
+		assert_eq!(check(&history, 1, "xyz", 1000), Ok(()));
+		// Bounds belong to their part
+		assert_eq!(check(&history, 2, "700", 1000), Ok(()));
+	}
+
+	#[test]
+	fn cooldown_after_wrong_answers() {
+		// The fourth wrong answer in the hour makes the site wait five minutes
+		let mut history = history();
+		assert_eq!(
+			check(&history, 2, "1", 430),
+			Err(Refusal::Cooldown {
+				remaining: Duration::from_secs(270)
+			})
+		);
+		assert_eq!(check(&history, 2, "1", 700), Ok(()));
+
+		history.record(submission(
+			2,
+			"1",
+			SubmissionResult::RateLimited {
+				wait: Duration::from_secs(90),
+			},
+			720,
+		));
+		assert!(matches!(
+			check(&history, 2, "2", 750),
+			Err(Refusal::Cooldown { remaining }) if remaining == Duration::from_secs(60)
+		));
+
+		history.record(submission(2, "2", SubmissionResult::Correct, 900));
+		assert!(matches!(
+			check(&history, 2, "3", 1000),
+			Err(Refusal::Solved { answer, .. }) if answer == "2"
+		));
+	}
+
+	#[test]
+	fn cooldown_grows_with_repeated_wrong_answers() {
+		assert_eq!(wrong_answer_cooldown(1), Duration::from_secs(60));
+		assert_eq!(wrong_answer_cooldown(4), Duration::from_secs(300));
+		assert_eq!(wrong_answer_cooldown(100), Duration::from_secs(900));
+
+		// The fifth wrong answer within the hour, after the four of history()
+		let mut history = history();
+		history.record(submission(2, "7", SubmissionResult::Wrong, 800));
+		assert!(matches!(
+			check(&history, 2, "8", 1000),
+			Err(Refusal::Cooldown { remaining }) if remaining == Duration::from_secs(400)
+		));
+		assert_eq!(check(&history, 2, "8", 1400), Ok(()));
+
+		// Wrong answers from more than an hour before don't count
+		history.record(submission(2, "8", SubmissionResult::Wrong, 5000));
+		assert_eq!(check(&history, 2, "9", 5060), Ok(()));
+	}
+
+	#[test]
+	fn refuses_parts_the_site_said_were_solved() {
+		let mut history = history();
+		history.record(submission(2, "7", SubmissionResult::AlreadySolved, 500));
+		assert_eq!(
+			check(&history, 2, "8", 1000),
+			Err(Refusal::AlreadySolved { part: 2 })
+		);
+		assert_eq!(check(&history, 1, "499", 1000), Ok(()));
+	}
+
+	#[test]
+	fn history_round_trip() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("submissions.json");
+		assert!(History::load(&path).unwrap().submissions.is_empty());
+
+		history().save(&path).unwrap();
+		let loaded = History::load(&path).unwrap();
+		assert_eq!(loaded.submissions, history().submissions);
+		assert!(std::fs::read_to_string(&path)
+			.unwrap()
+			.contains("\"verdict\": \"too_high\""));
+	}
+
+	#[test]
+	fn renders_attempts() {
+		assert_eq!(format_utc(UNLOCK), "2020-12-01 05:00:00");
+		assert_eq!(format_utc(951_782_400 + 3661), "2000-02-29 01:01:01");
+
+		let mut history = history();
+		history.record(submission(
+			2,
+			"1",
+			SubmissionResult::RateLimited {
+				wait: Duration::from_secs(30),
+			},
+			3600,
+		));
+		assert_eq!(
+			render(history.of_day(2020, Some(1)).skip(3)),
+			"Day  Part  Time (UTC)           Answer  Verdict
+  1     1  2020-12-01 05:06:40  500     too high
+  1     2  2020-12-01 06:00:00  1       rate limited, 30s left to wait
+"
+		);
+		assert_eq!(history.of_day(2020, Some(2)).count(), 0);
+		assert_eq!(history.of_day(2020, None).count(), 5);
+	}
+}
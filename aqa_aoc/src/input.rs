@@ -0,0 +1,150 @@
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use toml_edit::{DocumentMut, Item, Table};
+
+/// SHA-256 of every saved input, kept at the workspace root
+pub const INPUT_HASHES_FILE: &str = "input_hashes.toml";
+
+/// Beginnings of the plain text messages the site sends instead of an input
+const ERROR_MESSAGES: &[&str] = &[
+	"Puzzle inputs differ by user",
+	"Please don't repeatedly request this endpoint before it unlocks",
+	"404 Not Found",
+	"500 Internal Server Error",
+];
+
+#[derive(Debug, Error)]
+pub enum InputError {
+	#[error("the input is empty")]
+	Empty,
+	#[error("the site sent an error instead of the input: {0}")]
+	ErrorMessage(String),
+	#[error("the input is an HTML page")]
+	Html,
+}
+
+/// Refuses bodies that are obviously not a puzzle input
+pub fn validate(input: &str) -> Result<(), InputError> {
+	let trimmed = input.trim();
+	if trimmed.is_empty() {
+		return Err(InputError::Empty);
+	}
+
+	if let Some(message) = ERROR_MESSAGES.iter().find(|msg| trimmed.starts_with(*msg)) {
+		let line = trimmed.lines().next().unwrap_or(message);
+		return Err(InputError::ErrorMessage(line.to_string()));
+	}
+
+	let start = trimmed
+		.chars()
+		.take(16)
+		.collect::<String>()
+		.to_ascii_lowercase();
+	if start.starts_with("<!doctype") || start.starts_with("<html") {
+		return Err(InputError::Html);
+	}
+
+	Ok(())
+}
+
+#[derive(Debug, Error)]
+pub enum InputHashesError {
+	#[error("failed to read {0}")]
+	Read(PathBuf, #[source] io::Error),
+	#[error("failed to parse the input hashes")]
+	Parse(#[from] toml_edit::TomlError),
+}
+
+/// Hashes of saved inputs by year and day:
+///
+/// ```toml
+/// [2020]
+/// day_1 = "6b5c…"
+/// ```
+pub struct InputHashes {
+	doc: DocumentMut,
+}
+
+impl InputHashes {
+	pub fn parse(hashes: &str) -> Result<InputHashes, InputHashesError> {
+		Ok(InputHashes {
+			doc: hashes.parse()?,
+		})
+	}
+
+	/// Loads the manifest, a missing file is an empty manifest
+	pub fn load(path: &Path) -> Result<InputHashes, InputHashesError> {
+		match fs::read_to_string(path) {
+			Ok(v) => InputHashes::parse(&v),
+			Err(e) if e.kind() == ErrorKind::NotFound => InputHashes::parse(""),
+			Err(e) => Err(InputHashesError::Read(path.to_owned(), e)),
+		}
+	}
+
+	pub fn get(&self, year: u32, day: u32) -> Option<&str> {
+		self.doc
+			.get(&year.to_string())?
+			.get(format!("day_{}", day))
+			.and_then(Item::as_str)
+	}
+
+	pub fn set(&mut self, year: u32, day: u32, hash: &str) {
+		let year = self
+			.doc
+			.entry(&year.to_string())
+			.or_insert_with(|| Item::Table(Table::new()));
+		year[format!("day_{}", day).as_str()] = toml_edit::value(hash);
+	}
+}
+
+impl std::fmt::Display for InputHashes {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.doc)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{validate, InputError, InputHashes};
+
+	#[test]
+	fn accepts_inputs() {
+		assert!(validate("1721\n979\n366\n").is_ok());
+		assert!(validate("<x=-1, y=0, z=2>\n<x=2, y=-10, z=-7>\n").is_ok());
+	}
+
+	#[test]
+	fn refuses_error_pages() {
+		assert!(matches!(validate(" \n"), Err(InputError::Empty)));
+		assert!(matches!(
+			validate("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"),
+			Err(InputError::ErrorMessage(msg)) if msg.ends_with("get your puzzle input.")
+		));
+		assert!(matches!(
+			validate("<!DOCTYPE html>\n<html lang=\"en-us\"><head></head></html>"),
+			Err(InputError::Html)
+		));
+		assert!(matches!(
+			validate("<html><body>500</body></html>"),
+			Err(InputError::Html)
+		));
+	}
+
+	#[test]
+	fn hashes_keep_formatting() {
+		let manifest = "# Hashes of the saved inputs\n[2020]\nday_1 = \"aa\"\n";
+		let mut hashes = InputHashes::parse(manifest).unwrap();
+		assert_eq!(hashes.get(2020, 1), Some("aa"));
+		assert_eq!(hashes.get(2020, 2), None);
+
+		hashes.set(2020, 2, "bb");
+		hashes.set(2021, 1, "cc");
+		assert_eq!(
+			hashes.to_string(),
+			format!("{}day_2 = \"bb\"\n\n[2021]\nday_1 = \"cc\"\n", manifest)
+		);
+		assert_eq!(hashes.get(2021, 1), Some("cc"));
+	}
+}
@@ -0,0 +1,266 @@
+use serde::{Deserialize, Deserializer};
+use std::collections::{BTreeMap, HashMap};
+
+/// Private leaderboard as returned by `/{year}/leaderboard/private/view/{id}.json`
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+	pub event: String,
+	#[serde(deserialize_with = "number_or_string")]
+	pub owner_id: u64,
+	pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+	#[serde(deserialize_with = "number_or_string")]
+	pub id: u64,
+	pub name: Option<String>,
+	pub stars: u32,
+	pub local_score: u64,
+	#[serde(deserialize_with = "number_or_string")]
+	pub last_star_ts: u64,
+	/// Star timestamps by day and part
+	pub completion_day_level: BTreeMap<u32, BTreeMap<u32, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Star {
+	#[serde(deserialize_with = "number_or_string")]
+	pub get_star_ts: u64,
+}
+
+/// Older events send ids and timestamps as strings, newer ones as numbers
+fn number_or_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+	#[derive(Deserialize)]
+	#[serde(untagged)]
+	enum NumberOrString {
+		Number(u64),
+		String(String),
+	}
+
+	match NumberOrString::deserialize(deserializer)? {
+		NumberOrString::Number(v) => Ok(v),
+		NumberOrString::String(v) => v.parse().map_err(serde::de::Error::custom),
+	}
+}
+
+impl Member {
+	pub fn display_name(&self) -> String {
+		match &self.name {
+			Some(name) => name.clone(),
+			None => format!("(anonymous user #{})", self.id),
+		}
+	}
+
+	/// Seconds between getting the first and the second star of a day
+	pub fn part_2_delta(&self, day: u32) -> Option<u64> {
+		let stars = self.completion_day_level.get(&day)?;
+		let part_1 = stars.get(&1)?.get_star_ts;
+		let part_2 = stars.get(&2)?.get_star_ts;
+		Some(part_2.saturating_sub(part_1))
+	}
+
+	fn day_stars(&self, day: u32) -> usize {
+		self.completion_day_level
+			.get(&day)
+			.map(BTreeMap::len)
+			.unwrap_or(0)
+	}
+}
+
+impl Leaderboard {
+	pub fn parse(json: &str) -> serde_json::Result<Leaderboard> {
+		serde_json::from_str(json)
+	}
+
+	/// Members ordered the way the site ranks them, by local score and then by who got there first
+	pub fn ranked_members(&self) -> Vec<&Member> {
+		let mut members = self.members.values().collect::<Vec<_>>();
+		members.sort_by(|a, b| {
+			b.local_score
+				.cmp(&a.local_score)
+				.then(b.stars.cmp(&a.stars))
+				.then(a.last_star_ts.cmp(&b.last_star_ts))
+				.then(a.id.cmp(&b.id))
+		});
+		members
+	}
+
+	/// Last day anyone got a star for, days past 25 in the JSON are ignored
+	fn last_day(&self) -> u32 {
+		self.members
+			.values()
+			.filter_map(|member| {
+				member
+					.completion_day_level
+					.keys()
+					.filter(|day| **day <= 25)
+					.max()
+			})
+			.max()
+			.copied()
+			.unwrap_or(0)
+	}
+
+	/// Renders the stars of every member, followed by the time between their part 1 and part 2
+	/// stars for each day
+	pub fn render_table(&self) -> String {
+		let members = self.ranked_members();
+		let days = 1..=self.last_day();
+
+		let mut table = String::new();
+		if self.last_day() >= 10 {
+			let tens = days
+				.clone()
+				.map(|day| match day / 10 {
+					0 => ' ',
+					// SAFETY: last_day is at most 25
+					tens => std::char::from_digit(tens, 10).unwrap(),
+				})
+				.collect::<String>();
+			table.push_str(&format!("{:12}{}\n", "", tens));
+		}
+		let ones = days
+			.clone()
+			// SAFETY: day % 10 is a single digit
+			.map(|day| std::char::from_digit(day % 10, 10).unwrap())
+			.collect::<String>();
+		table.push_str(&format!("{:>4} {:>5}  {}  Name\n", "Rank", "Score", ones));
+
+		for (rank, member) in members.iter().enumerate() {
+			let stars = days
+				.clone()
+				.map(|day| match member.day_stars(day) {
+					0 => ' ',
+					1 => '.',
+					_ => '*',
+				})
+				.collect::<String>();
+			table.push_str(&format!(
+				"{:>4} {:>5}  {}  {}\n",
+				rank + 1,
+				member.local_score,
+				stars,
+				member.display_name()
+			));
+		}
+
+		let delta_days = days
+			.filter(|day| members.iter().any(|m| m.part_2_delta(*day).is_some()))
+			.collect::<Vec<_>>();
+		if delta_days.is_empty() {
+			return table;
+		}
+
+		let name_width = members
+			.iter()
+			.map(|member| member.display_name().chars().count())
+			.max()
+			.unwrap_or(0);
+		table.push_str("\nPart 1 -> Part 2\n");
+		table.push_str(&format!("{:1$}", "", name_width));
+		for day in &delta_days {
+			table.push_str(&format!(" {:>8}", format!("Day {}", day)));
+		}
+		table.push('\n');
+		for member in members {
+			let mut row = format!("{:1$}", member.display_name(), name_width);
+			for day in &delta_days {
+				let delta = member
+					.part_2_delta(*day)
+					.map(format_duration)
+					.unwrap_or_else(|| "-".to_string());
+				row.push_str(&format!(" {:>8}", delta));
+			}
+			table.push_str(row.trim_end());
+			table.push('\n');
+		}
+
+		table
+	}
+}
+
+/// Formats seconds using the two most significant units, e.g. `12m05s`
+fn format_duration(secs: u64) -> String {
+	match secs {
+		0..=59 => format!("{}s", secs),
+		60..=3599 => format!("{}m{:02}s", secs / 60, secs % 60),
+		3600..=86399 => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+		_ => format!("{}d{:02}h", secs / 86400, secs % 86400 / 3600),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{format_duration, Leaderboard};
+
+	const FIXTURE: &str = include_str!("../tests/fixtures/leaderboard.json");
+
+	#[test]
+	fn parses_fixture() {
+		let leaderboard = Leaderboard::parse(FIXTURE).unwrap();
+		assert_eq!(leaderboard.event, "2020");
+		assert_eq!(leaderboard.owner_id, 123456);
+		assert_eq!(leaderboard.members.len(), 3);
+
+		let ranked = leaderboard.ranked_members();
+		let names = ranked
+			.iter()
+			.map(|member| member.display_name())
+			.collect::<Vec<_>>();
+		assert_eq!(names, ["Alice", "aQaTL", "(anonymous user #345678)"]);
+
+		assert_eq!(ranked[1].part_2_delta(1), Some(177));
+		assert_eq!(ranked[1].part_2_delta(3), None);
+		assert_eq!(ranked[0].part_2_delta(3), Some(2120));
+	}
+
+	#[test]
+	fn numeric_ids_and_timestamps() {
+		let json = r#"{"event":"2023","owner_id":1,"members":{"1":{"id":1,"name":"a","stars":1,
+			"local_score":1,"last_star_ts":1701407000,
+			"completion_day_level":{"1":{"1":{"get_star_ts":1701407000,"star_index":0}}}}}}"#;
+		let leaderboard = Leaderboard::parse(json).unwrap();
+		assert_eq!(leaderboard.members["1"].last_star_ts, 1701407000);
+	}
+
+	#[test]
+	fn days_past_25_are_ignored() {
+		let json = r#"{"event":"2023","owner_id":1,"members":{"1":{"id":1,"name":"a","stars":2,
+			"local_score":1,"last_star_ts":1701407000,
+			"completion_day_level":{"1":{"1":{"get_star_ts":1701407000}},
+			"100":{"1":{"get_star_ts":1701407000},"2":{"get_star_ts":1701407060}}}}}}"#;
+		let leaderboard = Leaderboard::parse(json).unwrap();
+		assert_eq!(
+			leaderboard.render_table(),
+			"Rank Score  1  Name\n   1     1  .  a\n"
+		);
+	}
+
+	#[test]
+	fn renders_table() {
+		let leaderboard = Leaderboard::parse(FIXTURE).unwrap();
+		assert_eq!(
+			leaderboard.render_table(),
+			"Rank Score  123  Name
+   1    30  ***  Alice
+   2    26  **.  aQaTL
+   3     0       (anonymous user #345678)
+
+Part 1 -> Part 2
+                            Day 1    Day 2    Day 3
+Alice                       1m00s    2m30s   35m20s
+aQaTL                       2m57s   12m25s        -
+(anonymous user #345678)        -        -        -
+"
+		);
+	}
+
+	#[test]
+	fn durations() {
+		assert_eq!(format_duration(5), "5s");
+		assert_eq!(format_duration(725), "12m05s");
+		assert_eq!(format_duration(3 * 3600 + 60), "3h01m");
+		assert_eq!(format_duration(2 * 86400 + 3600), "2d01h");
+	}
+}
@@ -0,0 +1,19 @@
+pub mod answers;
+pub mod bench;
+pub mod cache;
+pub mod cfg;
+pub mod client;
+pub mod event;
+pub mod git;
+pub mod history;
+pub mod input;
+pub mod leaderboard;
+pub mod profile;
+pub mod puzzle;
+pub mod registry;
+pub mod status;
+pub mod submit;
+pub mod template;
+pub mod unlock;
+pub mod watch;
+pub mod workspace;
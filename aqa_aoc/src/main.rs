@@ -1,285 +1,1082 @@
+mod cli;
+
 use anyhow::Context;
-use serde::Deserialize;
+use aqa_aoc::answers::{Answers, ANSWERS_FILE};
+use aqa_aoc::cache::{self, InputCache, LeaderboardCache, Throttle};
+use aqa_aoc::cfg::AocCfg;
+use aqa_aoc::client::AocClient;
+use aqa_aoc::event;
+use aqa_aoc::git::{self, Git, GitCfg};
+use aqa_aoc::history::{self, History, Submission, SUBMISSIONS_FILE};
+use aqa_aoc::input::{self, InputHashes, INPUT_HASHES_FILE};
+use aqa_aoc::leaderboard::Leaderboard;
+use aqa_aoc::profile::{self, Profile};
+use aqa_aoc::puzzle;
+use aqa_aoc::registry;
+use aqa_aoc::status;
+use aqa_aoc::submit::SubmissionResult;
+use aqa_aoc::template::{TemplateVars, Templates, TEMPLATE_FILES};
+use aqa_aoc::unlock::{self, Backoff, Clock, SystemClock};
+use aqa_aoc::watch::{self, Step, Watcher};
+use aqa_aoc::workspace::{self, DayCrate, Manifest};
+use clap::{CommandFactory, Parser};
+use cli::{Cli, Command, FetchArgs};
+use std::collections::BTreeMap;
 use std::fs::{self, File};
 use std::io::Write;
-use std::path::Path;
-use std::process::Command;
-use thiserror::Error;
-use ureq::Cookie;
+use std::path::{Path, PathBuf};
+
+/// Workspace crate with the registry of the solutions, it runs them for `run` and `bench`
+const RUNNER_CRATE: &str = "aoc_runner";
+
+fn main() -> anyhow::Result<()> {
+	let cli = Cli::parse();
+	let (config, cli_year) = (cli.config.clone(), cli.year);
+	let command = cli.into_command();
+
+	if let Command::Completions { shell } = command {
+		clap_complete::generate(
+			shell,
+			&mut Cli::command(),
+			"aqa_aoc",
+			&mut std::io::stdout(),
+		);
+		return Ok(());
+	}
+
+	// Run by aoc_runner, which doesn't need the config
+	match command {
+		Command::Run { day, part } => {
+			let mut args = vec!["run".to_string(), format!("--day={}", day)];
+			args.extend(part.map(|part| format!("--part={}", part)));
+			return run_solutions(&config, cli_year, &args);
+		}
+		Command::Bench {
+			day,
+			iterations,
+			threshold,
+			save,
+		} => {
+			let mut args = vec![
+				"bench".to_string(),
+				format!("--iterations={}", iterations),
+				format!("--threshold={}", threshold),
+			];
+			args.extend(day.map(|day| format!("--day={}", day)));
+			if save {
+				args.push("--save".to_string());
+			}
+			return run_solutions(&config, cli_year, &args);
+		}
+		_ => (),
+	}
+
+	let cfg = AocCfg::load(&config)?;
+	let year = cli_year.unwrap_or(cfg.year);
+
+	match command {
+		Command::Watch { day } => return watch_day(year, cfg.year, day),
+		Command::Status { offline: true } => return show_status(year, cfg.year, None),
+		Command::Status { offline: false } => {
+			// The stars are the only part that needs the site
+			if let Err(e) = cfg.session_cookie() {
+				eprintln!("Warning: not fetching the stars, {:#}", e);
+				return show_status(year, cfg.year, None);
+			}
+		}
+		Command::Open { day } => return open_puzzle(&cfg, year, day),
+		Command::History { day } => return show_history(year, day),
+		Command::Fetch(FetchArgs {
+			day,
+			force,
+			dry_run: true,
+			..
+		}) => return print_plan(&cfg, year, day, Some(force), &[]),
+		Command::New {
+			day,
+			dry_run: true,
+			profile,
+		} => return print_plan(&cfg, year, day, None, &profile),
+		_ => (),
+	}
+
+	let (session_cookie, cookie_source) = cfg.session_cookie()?;
+	let cache_dir = cache::default_cache_dir().context("failed to find the cache directory")?;
+	let client = AocClient::new(&cfg.base_url(), &session_cookie)?
+		.with_throttle(Throttle::new(&cache_dir, cfg.request_interval()));
+
+	match command {
+		Command::Fetch(FetchArgs {
+			day: Some(day),
+			refresh: true,
+			..
+		}) => {
+			let day_crate = read_workspace()?.day_crate(year, day, cfg.year)?;
+
+			std::env::set_current_dir(day_crate.member())
+				.with_context(|| format!("{} does not exist", day_crate.member()))?;
+			save_puzzle(&client, year, day)
+		}
+		Command::Fetch(FetchArgs {
+			day,
+			force,
+			wait: true,
+			..
+		}) => {
+			let day = wait_for_unlock(&SystemClock, year, day)?;
+			let client = client.with_unlock_retry(
+				Backoff::at_least(cfg.request_interval()),
+				Box::new(SystemClock),
+			);
+			let input_cache = InputCache::new(&cache_dir, &session_cookie);
+			fetch_day(
+				&client,
+				&cfg,
+				year,
+				Some(day),
+				Some((&input_cache, force)),
+				&[],
+			)
+		}
+		Command::Fetch(FetchArgs {
+			force, all: true, ..
+		}) => {
+			let input_cache = InputCache::new(&cache_dir, &session_cookie);
+			fetch_all(&client, &cfg, year, &input_cache, force)
+		}
+		Command::Fetch(FetchArgs { day, force, .. }) => {
+			let input_cache = InputCache::new(&cache_dir, &session_cookie);
+			fetch_day(&client, &cfg, year, day, Some((&input_cache, force)), &[])
+		}
+		Command::New { day, profile, .. } => fetch_day(&client, &cfg, year, day, None, &profile),
+		Command::Submit {
+			day,
+			part,
+			answer,
+			force,
+		} => {
+			let history_path = Path::new(SUBMISSIONS_FILE);
+			let mut history = History::load(history_path)?;
+			if !force {
+				if let Err(refusal) = history.check(year, day, part, &answer, SystemClock.now()) {
+					anyhow::bail!(
+						"not submitting, {} (see `aqa_aoc history --day={}`, pass --force to submit anyway)",
+						refusal,
+						day
+					);
+				}
+			}
+
+			println!("Submitting day {} part {} answer: {}", day, part, answer);
+			let result = client.submit_answer(year, day, part, &answer)?;
+			println!("Result: {}", result);
+
+			history.record(Submission::new(
+				year,
+				day,
+				part,
+				&answer,
+				result,
+				SystemClock.now(),
+			));
+			history.save(history_path)?;
+
+			if result == SubmissionResult::Correct {
+				let mut answers = Answers::load(Path::new(ANSWERS_FILE))?;
+				answers.set(year, day, part, &answer)?;
+				answers.save(Path::new(ANSWERS_FILE))?;
+				println!("Answer recorded in {}", ANSWERS_FILE);
+
+				if cfg.git.is_some() {
+					if let Err(e) = commit_solution(year, cfg.year, day, part) {
+						eprintln!("Failed to commit the solution: {:#}", e);
+					}
+				}
+			}
 
-static AOC_ADDR: &str = "https://adventofcode.com";
+			std::process::exit(result.exit_code());
+		}
+		Command::Leaderboard { id, json, force } => {
+			let leaderboard_cache =
+				LeaderboardCache::new(&cache_dir, &session_cookie, cache::LEADERBOARD_TTL);
+			show_leaderboard(&client, &leaderboard_cache, year, id, json, force)
+		}
+		Command::Status { offline: false } => {
+			let stars = match client.get_event_page(year) {
+				Ok(page) => Some(event::stars(&page)),
+				Err(e) => {
+					eprintln!("Failed to fetch the stars: {:#}", e);
+					None
+				}
+			};
+			show_status(year, cfg.year, stars.as_ref())
+		}
+		Command::Whoami => {
+			println!("Using the session cookie from {}", cookie_source);
+			match client.whoami(year)? {
+				Some(user) => {
+					println!("Logged in as {}", user);
+					Ok(())
+				}
+				None => anyhow::bail!(
+					"the session cookie is expired or invalid, copy a new one from the browser"
+				),
+			}
+		}
+		Command::Run { .. }
+		| Command::Watch { .. }
+		| Command::Bench { .. }
+		| Command::Status { offline: true }
+		| Command::Open { .. }
+		| Command::History { .. }
+		| Command::Completions { .. } => {
+			unreachable!("handled before creating the client")
+		}
+	}
+}
 
-#[derive(Deserialize)]
-struct AocCfg {
+/// Works with the given day, or picks one when not set, creating its crate if needed.
+///
+/// The input is downloaded when `input` is set, `new` only creates the crate. New crates get
+/// the dependencies of `profiles`.
+fn fetch_day(
+	client: &AocClient,
+	cfg: &AocCfg,
 	year: u32,
-	session_cookie: String,
+	day: Option<u32>,
+	input: Option<(&InputCache, bool)>,
+	profiles: &[String],
+) -> anyhow::Result<()> {
+	let dependencies = profile::resolve(&cfg.profiles, profiles)?;
+	let root = std::env::current_dir()?;
+	let mut cargo_workspace = read_workspace()?;
+	let day = select_day(&mut cargo_workspace, cfg, year, day, input.is_some())?;
+
+	println!("Working with day: {}", day.get_day());
+
+	if cfg.git.is_some() {
+		match Git::new(&root).untrack_inputs() {
+			Ok(None) => (),
+			Ok(Some(inputs)) if inputs.is_empty() => {
+				println!("Committed \"{}\"", git::IGNORE_MESSAGE)
+			}
+			Ok(Some(inputs)) => {
+				println!(
+					"Committed \"{}\", the files are kept but no longer tracked:",
+					git::UNTRACK_MESSAGE
+				);
+				for input in inputs {
+					println!("  {}", input);
+				}
+			}
+			Err(e) => eprintln!("Failed to keep the inputs out of git: {:#}", e),
+		}
+	}
+
+	let day_crate_path = day.day_crate().member();
+
+	if let Day::NewDay(day_crate) = &day {
+		println!("Adding \"{}\" to workspace members", day_crate_path);
+
+		fs::write("Cargo.toml", cargo_workspace.to_string())?;
+
+		let templates = Templates::new(cfg.template_dir.as_deref());
+		create_crate_for_new_day(day_crate, &templates, &dependencies)?;
+	}
+
+	std::env::set_current_dir(&day_crate_path)?;
+
+	if let Day::NewDay(day_crate) = &day {
+		println!("Downloading day {} description...", day_crate.day);
+		if let Err(e) = save_puzzle(client, year, day_crate.day) {
+			eprintln!("Failed to save description: {:#}", e);
+		}
+
+		if let Some(git_cfg) = &cfg.git {
+			if let Err(e) = commit_new_day(&Git::new(&root), git_cfg, day_crate) {
+				eprintln!("Failed to commit the new day: {:#}", e);
+			}
+		}
+	}
+
+	let (input_cache, force) = match input {
+		Some(v) => v,
+		None => return Ok(()),
+	};
+
+	if !force && Path::new("input.txt").exists() {
+		println!(
+			"{}/input.txt already exists, pass --force to download it again",
+			day_crate_path
+		);
+		return Ok(());
+	}
+
+	let cached_input = if force {
+		None
+	} else {
+		input_cache.get(year, day.get_day())?
+	};
+	let day_1_input = match cached_input {
+		Some(input) => {
+			println!("Using cached day {} input", day.get_day());
+			input::validate(&input).context("the cached input is invalid, use --force")?;
+			input
+		}
+		None => {
+			println!("Downloading day {} input...", day.get_day());
+			let input = client.get_input(year, day.get_day())?;
+			input::validate(&input).context("refusing to save the input")?;
+			input_cache.put(year, day.get_day(), &input)?;
+			input
+		}
+	};
+
+	let hashes_path = Path::new(day.day_crate().workspace_root()).join(INPUT_HASHES_FILE);
+	let mut input_hashes = InputHashes::load(&hashes_path)?;
+	let hash = cache::sha256_hex(day_1_input.as_bytes());
+	match input_hashes.get(year, day.get_day()) {
+		Some(recorded) if recorded != hash => eprintln!(
+			"Warning: the day {} input differs from the one saved before (sha256 {} -> {})",
+			day.get_day(),
+			recorded,
+			hash
+		),
+		_ => (),
+	}
+
+	let mut input_file = File::create("input.txt")?;
+	input_file.write_all(day_1_input.as_bytes())?;
+
+	input_hashes.set(year, day.get_day(), &hash);
+	fs::write(&hashes_path, input_hashes.to_string())?;
+
+	println!("Input saved to {}/input.txt", day_crate_path);
+
+	Ok(())
+}
+
+/// Shows a countdown until the given day unlocks, the next day to unlock when not set
+fn wait_for_unlock(clock: &dyn Clock, year: u32, day: Option<u32>) -> anyhow::Result<u32> {
+	let day = match day {
+		Some(v) => v,
+		None => unlock::next_unlock(year, clock.now())
+			.with_context(|| format!("every day of {} is already unlocked", year))?,
+	};
+
+	let unlock_time = unlock::unlock_time(year, day);
+	if unlock_time <= clock.now() {
+		return Ok(day);
+	}
+
+	println!("Waiting for day {} of {} to unlock", day, year);
+	unlock::wait_until(clock, unlock_time, |remaining| {
+		print!("\rUnlocks in {} ", unlock::format_countdown(remaining));
+		// The countdown is cosmetic, a failed flush only delays it
+		let _ = std::io::stdout().flush();
+	});
+	println!();
+
+	Ok(day)
 }
 
-#[derive(Debug, Error)]
-pub enum CargoTomlParserError {
-	#[error("failed to get \"workspace\" section")]
-	FailedToGetWorkspace,
-	#[error("failed to get \"members\" array")]
-	FailedToGetMembers,
-	#[error("failed to get \"dependencies\" section")]
-	FailedToGetDependencies,
+fn read_workspace() -> anyhow::Result<Manifest> {
+	Manifest::parse(&fs::read_to_string("Cargo.toml").context("failed to read Cargo.toml")?)
+		.context("failed to parse Cargo.toml")
 }
 
-#[derive(Debug)]
-struct CliApp {
-	day: Option<u32>,
+/// Runs `args` with [RUNNER_CRATE], the crate that depends on the day crates, so a day that
+/// doesn't compile doesn't break aqa_aoc itself
+fn run_solutions(config: &Path, year: Option<u32>, args: &[String]) -> anyhow::Result<()> {
+	let status = std::process::Command::new("cargo")
+		.args(["run", "--release", "-q", "-p", RUNNER_CRATE, "--"])
+		.arg("--config")
+		.arg(config)
+		.args(year.map(|year| format!("--year={}", year)))
+		.args(args)
+		.status()
+		.context("failed to run cargo")?;
+	if !status.success() {
+		anyhow::bail!("{} failed ({})", RUNNER_CRATE, status);
+	}
+	Ok(())
 }
 
-impl CliApp {
-	fn from_args() -> anyhow::Result<CliApp> {
-		let mut day = None;
+/// Rebuilds the day, runs its tests and then the solution after every change of its sources or
+/// inputs. A change while a step runs cancels the run and starts a new one.
+fn watch_day(year: u32, default_year: u32, day: u32) -> anyhow::Result<()> {
+	let day_crate = read_workspace()?.day_crate(year, day, default_year)?;
+	let member = PathBuf::from(day_crate.member());
+	let mut watcher = Watcher::new(
+		["src", "input.txt", "examples", "Cargo.toml"]
+			.iter()
+			.map(|path| member.join(path))
+			.collect(),
+	);
 
-		for arg in std::env::args().skip(1) {
-			if let Some(arg) = arg.strip_prefix("--day=") {
-				day = Some(arg.parse::<u32>()?);
+	println!("Watching {}, press Ctrl-C to stop", member.display());
+	loop {
+		println!();
+		println!("Running day {}...", day);
+		match watch_run(&day_crate, &mut watcher)? {
+			Some(summary) => {
+				print!("{}", summary);
+				watcher.wait_for_change(&SystemClock);
 			}
+			None => {
+				println!("Change detected, cancelled the run");
+				watcher.settle(&SystemClock);
+			}
+		}
+	}
+}
+
+/// Builds, tests and runs the day once, `None` when a change cancelled the run
+fn watch_run(day_crate: &DayCrate, watcher: &mut Watcher) -> anyhow::Result<Option<String>> {
+	let name = day_crate.name();
+	let mut steps = Vec::new();
+
+	let build = watch::run_cancellable(
+		std::process::Command::new("cargo").args([
+			"build",
+			"--release",
+			"-p",
+			&name,
+			"--message-format=json-render-diagnostics",
+		]),
+		|| watcher.changed(),
+	)
+	.context("failed to run cargo build")?;
+	let build = match build {
+		Some(v) => v,
+		None => return Ok(None),
+	};
+	let binary = watch::executable(&build.lines).filter(|_| build.success);
+	steps.push(Step {
+		name: "build".to_string(),
+		passed: binary.is_some(),
+		detail: String::new(),
+		time: build.elapsed,
+	});
+	let binary = match binary {
+		Some(v) => v,
+		None => {
+			let diagnostics = build
+				.lines
+				.into_iter()
+				.filter(|line| !line.text.starts_with('{'))
+				.collect::<Vec<_>>();
+			print_output_tail(&diagnostics);
+			return Ok(Some(watch::render_summary(&steps, &[])));
 		}
+	};
+
+	// Release like the build, so the tests reuse its dependencies instead of compiling them again
+	let tests = watch::run_cancellable(
+		std::process::Command::new("cargo").args(["test", "--release", "-p", &name]),
+		|| watcher.changed(),
+	)
+	.context("failed to run cargo test")?;
+	let tests = match tests {
+		Some(v) => v,
+		None => return Ok(None),
+	};
+	if !tests.success {
+		print_output_tail(&tests.lines);
+	}
+	let (passed, failed) = watch::test_counts(&tests.lines);
+	steps.push(Step {
+		name: "tests".to_string(),
+		passed: tests.success,
+		detail: format!("{} passed, {} failed", passed, failed),
+		time: tests.elapsed,
+	});
 
-		Ok(CliApp { day })
+	// Failing tests don't stop the run, the answers are still worth seeing
+	let solution = watch::run_cancellable(&mut std::process::Command::new(&binary), || {
+		watcher.changed()
+	})
+	.with_context(|| format!("failed to run {}", binary.display()))?;
+	let solution = match solution {
+		Some(v) => v,
+		None => return Ok(None),
+	};
+	if !solution.success {
+		print_output_tail(&solution.lines);
 	}
+	steps.push(Step {
+		name: "run".to_string(),
+		passed: solution.success,
+		detail: String::new(),
+		time: solution.elapsed,
+	});
+
+	Ok(Some(watch::render_summary(
+		&steps,
+		&watch::part_answers(&solution.lines),
+	)))
 }
 
-fn main() -> anyhow::Result<()> {
-	let cli = CliApp::from_args()?;
+/// Prints the end of the output of a failed step
+fn print_output_tail(lines: &[watch::Line]) {
+	const TAIL: usize = 30;
+	if lines.len() > TAIL {
+		println!("  ...");
+	}
+	for line in &lines[lines.len().saturating_sub(TAIL)..] {
+		println!("  {}", line.text);
+	}
+}
+
+fn show_history(year: u32, day: Option<u32>) -> anyhow::Result<()> {
+	let history = History::load(Path::new(SUBMISSIONS_FILE))?;
+	let mut submissions = history.of_day(year, day).peekable();
+	if submissions.peek().is_none() {
+		match day {
+			Some(day) => println!("No answers submitted for day {} of {}", day, year),
+			None => println!("No answers submitted for {}", year),
+		}
+		return Ok(());
+	}
 
-	let cfg: AocCfg =
-		toml::from_slice(&std::fs::read("aoc_cfg.toml").context("failed to read aoc_cfg.toml")?)
-			.context("failed to parse aoc_cfg.toml")?;
+	print!("{}", history::render(submissions));
+	Ok(())
+}
 
-	let mut cargo_workspace: toml::Value =
-		toml::from_slice(&std::fs::read("Cargo.toml").context("failed to read Cargo.toml")?)
-			.context("failed to parse Cargo.toml")?;
+fn show_status(
+	year: u32,
+	default_year: u32,
+	stars: Option<&BTreeMap<u32, u32>>,
+) -> anyhow::Result<()> {
+	let day_crates = read_workspace()?.day_crates(year, default_year)?;
+	let answers = Answers::load(Path::new(ANSWERS_FILE))?;
 
-	let day = match cli.day {
-		Some(specific_day) => work_with_specific_day(&mut cargo_workspace, specific_day)?,
-		None => get_day_we_are_working_with(&mut cargo_workspace)?,
+	println!("Advent of Code {}", year);
+	print!(
+		"{}",
+		status::render(&status::collect(
+			Path::new("."),
+			year,
+			&day_crates,
+			&answers,
+			stars
+		))
+	);
+
+	Ok(())
+}
+
+fn open_puzzle(cfg: &AocCfg, year: u32, day: Option<u32>) -> anyhow::Result<()> {
+	let day = match day {
+		Some(v) => v,
+		None => last_day(&read_workspace()?, year, cfg.year)?
+			.with_context(|| format!("no days of {} in the workspace, pass --day", year))?,
 	};
 
-	println!("Working with day: {}", day.get_day());
+	let url = format!("{}/{}/day/{}", cfg.base_url(), year, day);
+	println!("Opening {}", url);
 
-	let day_crate_name = day.get_crate_name();
+	let mut command = if cfg!(target_os = "windows") {
+		let mut command = std::process::Command::new("cmd");
+		command.args(["/C", "start", ""]);
+		command
+	} else if cfg!(target_os = "macos") {
+		std::process::Command::new("open")
+	} else {
+		std::process::Command::new("xdg-open")
+	};
+	let status = command
+		.arg(&url)
+		.status()
+		.context("failed to start the browser")?;
+	if !status.success() {
+		anyhow::bail!("failed to open the browser: {}", status);
+	}
 
-	if let Day::NewDay(day) = day {
-		println!("Adding \"day_{}\" to workspace members", day);
+	Ok(())
+}
 
-		let mut cargo_workspace_file = File::create("Cargo.toml")?;
-		let cargo_workspace_str = toml::to_string_pretty(&cargo_workspace)?;
-		cargo_workspace_file.write_all(cargo_workspace_str.as_bytes())?;
+fn show_leaderboard(
+	client: &AocClient,
+	leaderboard_cache: &LeaderboardCache,
+	year: u32,
+	id: u64,
+	json: bool,
+	force: bool,
+) -> anyhow::Result<()> {
+	let cached = if force {
+		None
+	} else {
+		leaderboard_cache.get(year, id)?
+	};
+	let leaderboard_json = match cached {
+		Some(v) => v,
+		None => {
+			let leaderboard_json = client.get_private_leaderboard(year, id)?;
+			leaderboard_cache.put(year, id, &leaderboard_json)?;
+			leaderboard_json
+		}
+	};
 
-		create_crate_for_new_day(&day_crate_name)?;
+	if json {
+		println!("{}", leaderboard_json.trim_end());
+		return Ok(());
 	}
 
-	std::env::set_current_dir(&day_crate_name)?;
+	let leaderboard =
+		Leaderboard::parse(&leaderboard_json).context("failed to parse the leaderboard")?;
+	print!("{}", leaderboard.render_table());
 
-	println!("Downloading day {} input...", day.get_day());
+	Ok(())
+}
 
-	let day_1_input = get_input(cfg.year, day.get_day(), &cfg.session_cookie)?;
+/// Downloads the input of every unlocked day of `year`, days with an input are skipped
+/// unless `force` is set
+fn fetch_all(
+	client: &AocClient,
+	cfg: &AocCfg,
+	year: u32,
+	input_cache: &InputCache,
+	force: bool,
+) -> anyhow::Result<()> {
+	let root = std::env::current_dir()?;
+	let now = SystemClock.now();
+	let unlocked = (1..=25)
+		.take_while(|day| unlock::unlock_time(year, *day) <= now)
+		.collect::<Vec<_>>();
 
-	let mut input_file = File::create("input.txt")?;
-	input_file.write_all(day_1_input.as_bytes())?;
+	let (mut created, mut downloaded, mut skipped) = (0, 0, 0);
+	let mut failed = Vec::new();
+	for day in unlocked {
+		let day_crate = read_workspace()?.day_crate(year, day, cfg.year)?;
+		let crate_dir = Path::new(&day_crate.member()).to_owned();
+		let crate_exists = crate_dir.is_dir();
+		if crate_exists && !force && crate_dir.join("input.txt").exists() {
+			skipped += 1;
+			continue;
+		}
+
+		let result = fetch_day(
+			client,
+			cfg,
+			year,
+			Some(day),
+			Some((input_cache, force)),
+			&[],
+		);
+		// fetch_day works from inside the crate directory
+		std::env::set_current_dir(&root)?;
+		match result {
+			Ok(()) => {
+				if !crate_exists {
+					created += 1;
+				}
+				downloaded += 1;
+			}
+			Err(e) => {
+				eprintln!("Failed to fetch day {}: {:#}", day, e);
+				failed.push(day);
+			}
+		}
+		println!();
+	}
+
+	println!(
+		"{}: {} crates created, {} inputs saved, {} days skipped, {} failed",
+		year,
+		created,
+		downloaded,
+		skipped,
+		failed.len()
+	);
+	if !failed.is_empty() {
+		anyhow::bail!(
+			"failed to fetch days {}",
+			failed
+				.iter()
+				.map(ToString::to_string)
+				.collect::<Vec<_>>()
+				.join(", ")
+		);
+	}
 
-	println!("Input saved to {}/input.txt", day_crate_name);
+	Ok(())
+}
+
+/// Picks the day `fetch` (`with_input`) or `new` works with, adding a new day to the
+/// in-memory manifest
+fn select_day(
+	cargo_workspace: &mut Manifest,
+	cfg: &AocCfg,
+	year: u32,
+	day: Option<u32>,
+	with_input: bool,
+) -> anyhow::Result<Day> {
+	let day = match day {
+		Some(specific_day) => {
+			work_with_specific_day(cargo_workspace, year, cfg.year, specific_day)?
+		}
+		None if !with_input => next_new_day(cargo_workspace, year, cfg.year)?,
+		None => get_day_we_are_working_with(cargo_workspace, year, cfg.year)?,
+	};
+
+	if let Day::CreatedBefore(day_crate) = &day {
+		if !with_input {
+			anyhow::bail!("{} already exists", day_crate.member());
+		}
+	}
+
+	Ok(day)
+}
+
+/// Prints what `fetch` (`force` is set) or `new` would do, without changing anything
+fn print_plan(
+	cfg: &AocCfg,
+	year: u32,
+	day: Option<u32>,
+	force: Option<bool>,
+	profiles: &[String],
+) -> anyhow::Result<()> {
+	let dependencies = profile::resolve(&cfg.profiles, profiles)?;
+	let mut cargo_workspace = read_workspace()?;
+	let day = select_day(&mut cargo_workspace, cfg, year, day, force.is_some())?;
+	let day_crate = day.day_crate();
+	let crate_path = day_crate.member();
+
+	println!("Dry run, nothing is changed");
+	println!("Day: {} of {}", day_crate.day, year);
+	if cfg.git.is_some() {
+		let git = Git::new(&std::env::current_dir()?);
+		let inputs = git.tracked_inputs()?;
+		let outdated = git.gitignore_outdated()?;
+		if outdated {
+			println!("Git: adds the managed block ignoring the inputs to .gitignore");
+		}
+		if !inputs.is_empty() {
+			println!("Git: removes the inputs from the index, the files are kept:");
+			for input in &inputs {
+				println!("  {}", input);
+			}
+		}
+		if outdated || !inputs.is_empty() {
+			println!(
+				"Git: commits that alone as \"{}\"",
+				if inputs.is_empty() {
+					git::IGNORE_MESSAGE
+				} else {
+					git::UNTRACK_MESSAGE
+				}
+			);
+		}
+	}
+	match &day {
+		Day::NewDay(day_crate) => {
+			println!(
+				"Cargo.toml: adds \"{}\" to the workspace members",
+				crate_path
+			);
+			println!("Creates crate \"{}\":", day_crate.name());
+			for (_, path) in TEMPLATE_FILES {
+				println!("  {}/{}", crate_path, path);
+			}
+			println!("  {}/src/examples.rs", crate_path);
+			println!(
+				"Adds the dependencies: {}",
+				dependencies.keys().cloned().collect::<Vec<_>>().join(", ")
+			);
+			println!(
+				"Registers the crate in {0}/Cargo.toml and {0}/src/registry.rs",
+				RUNNER_CRATE
+			);
+			println!(
+				"Downloads the description to {0}/README.md and its examples to {0}/examples/",
+				crate_path
+			);
+			if let Some(git_cfg) = &cfg.git {
+				if git_cfg.branch_per_day {
+					println!(
+						"Git: switches to branch {}, created when missing",
+						day_crate.name().replace('_', "-")
+					);
+				}
+				println!(
+					"Git: commits Cargo.toml, the runner and {} as \"Add {} day {}\", nothing else staged is included",
+					crate_path, day_crate.year, day_crate.day
+				);
+			}
+		}
+		Day::CreatedBefore(_) => println!("Cargo.toml: unchanged, {} exists", crate_path),
+	}
+
+	match force {
+		None => (),
+		Some(false) if Path::new(&crate_path).join("input.txt").exists() => println!(
+			"Keeps {}/input.txt, pass --force to download it again",
+			crate_path
+		),
+		Some(force) => {
+			println!(
+				"Writes the input to {}/input.txt, {}",
+				crate_path,
+				if force {
+					"downloaded again"
+				} else {
+					"from the cache when available"
+				}
+			);
+			println!("Records its hash in {}", INPUT_HASHES_FILE);
+		}
+	}
 
 	Ok(())
 }
 
-#[derive(Copy, Clone)]
 enum Day {
-	NewDay(u32),
-	CreatedBefore(u32),
+	NewDay(DayCrate),
+	CreatedBefore(DayCrate),
 }
 
 impl Day {
-	fn get_day(&self) -> u32 {
+	fn day_crate(&self) -> &DayCrate {
 		match self {
-			Day::NewDay(day) | Day::CreatedBefore(day) => *day,
+			Day::NewDay(day_crate) | Day::CreatedBefore(day_crate) => day_crate,
 		}
 	}
 
-	fn get_crate_name(&self) -> String {
-		format!("day_{}", self.get_day())
+	fn get_day(&self) -> u32 {
+		self.day_crate().day
 	}
 }
 
-fn get_day_we_are_working_with(cargo_workspace: &mut toml::Value) -> anyhow::Result<Day> {
-	let workspace = cargo_workspace
-		.get_mut("workspace")
-		.ok_or(CargoTomlParserError::FailedToGetWorkspace)?;
-	let members = workspace
-		.get_mut("members")
-		.ok_or(CargoTomlParserError::FailedToGetMembers)?
-		.as_array_mut()
-		.ok_or(CargoTomlParserError::FailedToGetMembers)?;
-
-	let mut last_day_without_input = None;
-	for dir in fs::read_dir(".")?
-		.filter_map(Result::ok)
-		.filter(|v| v.path().is_dir())
-	{
-		let day = dir
-			.path()
-			.file_name()
-			.and_then(|name| name.to_str())
-			.and_then(|name| name.strip_prefix("day_"))
-			.and_then(|day| day.parse::<u32>().ok());
-
-		let day = match day {
-			Some(v) => v,
-			None => continue,
-		};
-
-		if !dir.path().join("input.txt").exists() {
-			last_day_without_input = Some(day);
-			break;
-		}
+fn get_day_we_are_working_with(
+	cargo_workspace: &mut Manifest,
+	year: u32,
+	default_year: u32,
+) -> anyhow::Result<Day> {
+	let nested = cargo_workspace.is_nested(year, default_year)?;
+	let year_dir = if nested {
+		PathBuf::from(format!("y{}", year))
+	} else {
+		PathBuf::from(".")
+	};
+
+	match workspace::first_day_without_input(&year_dir)? {
+		Some(day) => Ok(Day::CreatedBefore(DayCrate { year, day, nested })),
+		None => next_new_day(cargo_workspace, year, default_year),
 	}
+}
 
-	match last_day_without_input {
-		Some(v) => Ok(Day::CreatedBefore(v)),
-		None => {
-			let day = members
-				.iter()
-				.filter_map(toml::Value::as_str)
-				.filter_map(|str| str.strip_prefix("day_"))
-				.filter_map(|day| day.parse::<u32>().ok())
-				.max()
-				.unwrap_or(0) + 1;
-
-			members.push(toml::Value::String(format!("day_{}", day)));
-			Ok(Day::NewDay(day))
-		}
+/// Adds the day after the last day crate of the year to the workspace members
+fn next_new_day(
+	cargo_workspace: &mut Manifest,
+	year: u32,
+	default_year: u32,
+) -> anyhow::Result<Day> {
+	let day = last_day(cargo_workspace, year, default_year)?.unwrap_or(0) + 1;
+	if day > 25 {
+		anyhow::bail!("every day of {} already exists", year);
 	}
+
+	let day_crate = cargo_workspace.day_crate(year, day, default_year)?;
+	cargo_workspace.insert_member(&day_crate.member())?;
+	Ok(Day::NewDay(day_crate))
+}
+
+fn last_day(
+	cargo_workspace: &Manifest,
+	year: u32,
+	default_year: u32,
+) -> anyhow::Result<Option<u32>> {
+	Ok(cargo_workspace
+		.day_crates(year, default_year)?
+		.iter()
+		.map(|day_crate| day_crate.day)
+		.max())
 }
 
 fn work_with_specific_day(
-	cargo_workspace: &mut toml::Value,
+	cargo_workspace: &mut Manifest,
+	year: u32,
+	default_year: u32,
 	specific_day: u32,
 ) -> anyhow::Result<Day> {
-	let workspace = cargo_workspace
-		.get_mut("workspace")
-		.ok_or(CargoTomlParserError::FailedToGetWorkspace)?;
-	let members = workspace
-		.get_mut("members")
-		.ok_or(CargoTomlParserError::FailedToGetMembers)?
-		.as_array_mut()
-		.ok_or(CargoTomlParserError::FailedToGetMembers)?;
-
-	let mut day_project = None;
-	for dir in fs::read_dir(".")?
-		.filter_map(Result::ok)
-		.filter(|v| v.path().is_dir())
-	{
-		let day = dir
-			.path()
-			.file_name()
-			.and_then(|name| name.to_str())
-			.and_then(|name| name.strip_prefix("day_"))
-			.and_then(|day| day.parse::<u32>().ok());
-
-		match day {
-			Some(day) if day == specific_day => {
-				day_project = Some(day);
-				break;
-			}
-			_ => (),
-		};
-	}
+	let day_crate = cargo_workspace.day_crate(year, specific_day, default_year)?;
 
-	match day_project {
-		Some(v) => Ok(Day::CreatedBefore(v)),
-		None => {
-			members.push(toml::Value::String(format!("day_{}", specific_day)));
-			Ok(Day::NewDay(specific_day))
-		}
+	if Path::new(&day_crate.member()).is_dir() {
+		Ok(Day::CreatedBefore(day_crate))
+	} else {
+		cargo_workspace.insert_member(&day_crate.member())?;
+		Ok(Day::NewDay(day_crate))
 	}
 }
 
-fn create_crate_for_new_day(new_crate_name: &str) -> anyhow::Result<()> {
+fn create_crate_for_new_day(
+	day_crate: &DayCrate,
+	templates: &Templates,
+	dependencies: &Profile,
+) -> anyhow::Result<()> {
+	let new_crate_name = day_crate.name();
+	let crate_path = day_crate.member();
 	println!("Creating new crate (\"{}\")", new_crate_name);
 
-	let cargo_new_status = Command::new("cargo")
-		.args(&["new", &new_crate_name])
-		.status()?;
-	if !cargo_new_status.success() {
-		return Err(anyhow::anyhow!(
-			"cargo new failed with code {}",
-			cargo_new_status.code().unwrap_or_default()
-		));
+	let crate_dir = Path::new(&crate_path);
+	if crate_dir.exists() {
+		anyhow::bail!("{} already exists", crate_dir.display());
 	}
+	fs::create_dir_all(crate_dir.join("src"))?;
+
+	let vars = TemplateVars {
+		day: day_crate.day,
+		year: day_crate.year,
+		crate_name: &new_crate_name,
+		crate_path: &crate_path,
+		workspace_root: day_crate.workspace_root(),
+	};
+	for (template, path) in TEMPLATE_FILES {
+		let rendered = templates.render(template, &vars)?;
+		fs::write(crate_dir.join(path), rendered)?;
+	}
+	fs::write(
+		crate_dir.join("src").join("examples.rs"),
+		puzzle::examples_test_module(&[], None),
+	)?;
+
+	register_day(day_crate)?;
 
-	add_useful_deps(&new_crate_name)?;
+	add_useful_deps(day_crate, dependencies)?;
 
 	Ok(())
 }
 
-fn add_useful_deps(new_crate_name: &str) -> anyhow::Result<()> {
-	let cargo_toml_path = Path::new(new_crate_name).join("Cargo.toml");
-	let mut cargo_toml: toml::Value = toml::from_slice(
-		&std::fs::read(&cargo_toml_path)
+fn add_useful_deps(day_crate: &DayCrate, dependencies: &Profile) -> anyhow::Result<()> {
+	let cargo_toml_path = Path::new(&day_crate.member()).join("Cargo.toml");
+	let mut cargo_toml = Manifest::parse(
+		&std::fs::read_to_string(&cargo_toml_path)
 			.with_context(|| format!("failed to read {}", cargo_toml_path.display()))?,
 	)
 	.with_context(|| format!("failed to parse {}", cargo_toml_path.display()))?;
 
-	let dependencies = cargo_toml
-		.get_mut("dependencies")
-		.ok_or(CargoTomlParserError::FailedToGetDependencies)?
-		.as_table_mut()
-		.ok_or(CargoTomlParserError::FailedToGetDependencies)?;
-
-	dependencies.insert("anyhow".to_string(), toml::Value::String("1.0".to_string()));
-	dependencies.insert(
-		"itertools".to_string(),
-		toml::Value::String("0.9".to_string()),
-	);
-	dependencies.insert("regex".to_string(), toml::Value::String("1.4".to_string()));
-	dependencies.insert("nom".to_string(), toml::Value::String("7.0.0".to_string()));
+	// The templates use anyhow
+	cargo_toml.set_dependency("anyhow", toml_edit::value("1.0"))?;
+	for (name, dependency) in dependencies {
+		cargo_toml.set_dependency(name, dependency.to_item(day_crate.workspace_root()))?;
+	}
 
-	let mut cargo_toml_file = File::create(&cargo_toml_path)?;
-	let cargo_toml_str = toml::to_string_pretty(&cargo_toml)?;
-	cargo_toml_file.write_all(cargo_toml_str.as_bytes())?;
+	fs::write(&cargo_toml_path, cargo_toml.to_string())?;
 
 	println!("[dependencies] updated");
 
 	Ok(())
 }
 
-fn get_input(year: u32, day: u32, cookie: &str) -> anyhow::Result<String> {
-	let cookie = Cookie::build("session", cookie)
-		.domain("adventofcode.com")
-		.path("/")
-		.secure(true)
-		.finish()
-		.into_owned();
-
-	let agent = ureq::agent();
-	agent.set_cookie(cookie);
-
-	let req_adr = format!(
-		"{addr}/{year}/day/{day}/input",
-		addr = AOC_ADDR,
-		year = year,
-		day = day
-	);
-	println!("Request addr: {}", req_adr);
-	let mut request = agent.get(&req_adr);
-	let response = request.call();
+/// Makes the new day crate available to `aqa_aoc run` and `aqa_aoc bench`
+fn register_day(day_crate: &DayCrate) -> anyhow::Result<()> {
+	let new_crate_name = day_crate.name();
+	let manifest_path = Path::new(RUNNER_CRATE).join("Cargo.toml");
+	let mut manifest = Manifest::parse(
+		&fs::read_to_string(&manifest_path)
+			.with_context(|| format!("failed to read {}", manifest_path.display()))?,
+	)?;
+	let mut dependency = toml_edit::InlineTable::new();
+	dependency.insert("path", format!("../{}", day_crate.member()).into());
+	manifest.set_dependency(&new_crate_name, toml_edit::value(dependency))?;
+	fs::write(&manifest_path, manifest.to_string())?;
+
+	let registry_path = Path::new(RUNNER_CRATE).join("src").join("registry.rs");
+	let registry_src = fs::read_to_string(&registry_path)
+		.with_context(|| format!("failed to read {}", registry_path.display()))?;
+	fs::write(
+		&registry_path,
+		registry::register(
+			&registry_src,
+			day_crate.year,
+			day_crate.day,
+			&new_crate_name,
+			&day_crate.member(),
+		)?,
+	)?;
+
+	println!("Registered {} in {}", new_crate_name, RUNNER_CRATE);
+
+	Ok(())
+}
+
+/// Commits the files of a new day crate, on its own branch when `branch_per_day` is set
+fn commit_new_day(git: &Git, git_cfg: &GitCfg, day_crate: &DayCrate) -> anyhow::Result<()> {
+	if git_cfg.branch_per_day {
+		// day-N, or yYYYY-day-N for other years than the default one
+		let branch = day_crate.name().replace('_', "-");
+		git.switch_branch(&branch)?;
+		println!("Switched to branch {}", branch);
+	}
+
+	let member = day_crate.member();
+	let runner_manifest = format!("{}/Cargo.toml", RUNNER_CRATE);
+	let runner_registry = format!("{}/src/registry.rs", RUNNER_CRATE);
+	let paths = ["Cargo.toml", &runner_manifest, &runner_registry, &member];
+	let message = format!("Add {} day {}", day_crate.year, day_crate.day);
+	if git.commit(&paths, &message)? {
+		println!("Committed \"{}\"", message);
+	}
+
+	Ok(())
+}
+
+/// Commits the day crate and the answers ledger after an accepted answer, run from the
+/// workspace root
+fn commit_solution(year: u32, default_year: u32, day: u32, part: u32) -> anyhow::Result<()> {
+	let day_crate = read_workspace()?.day_crate(year, day, default_year)?;
+	let git = Git::new(&std::env::current_dir()?);
+
+	let member = day_crate.member();
+	let message = format!("Solve {} day {} part {}", year, day, part);
+	if git.commit(&[&member, ANSWERS_FILE], &message)? {
+		println!("Committed \"{}\"", message);
+	}
+
+	Ok(())
+}
+
+/// Writes the puzzle description of the given day to README.md in the current directory,
+/// together with its examples and their generated test module
+fn save_puzzle(client: &AocClient, year: u32, day: u32) -> anyhow::Result<()> {
+	let page = client.get_puzzle_page(year, day)?;
+	let description = puzzle::description_to_markdown(&page, client.addr())?;
 
-	if response.synthetic() {
-		// SAFETY: synthetic returns true only when error is Some
-		return Err(response.into_synthetic_error().unwrap().into());
+	let mut readme_file = File::create("README.md")?;
+	readme_file.write_all(description.as_bytes())?;
+	println!("Description saved to README.md");
+
+	let examples = puzzle::extract_examples(&page);
+	if examples.is_empty() {
+		return Ok(());
 	}
 
-	if response.error() {
-		return Err(anyhow::anyhow!(
-			"Failed to fetch input: {}",
-			response.status_line()
-		));
+	fs::create_dir_all("examples")?;
+	for (idx, example) in examples.iter().enumerate() {
+		let example_path = format!("examples/example_{}.txt", idx + 1);
+		let mut example_file = File::create(&example_path)?;
+		example_file.write_all(example.input.as_bytes())?;
 	}
+	println!("{} examples saved to examples/", examples.len());
 
-	response.into_string().map_err(Into::into)
+	let tests_path = Path::new("src").join("examples.rs");
+	let existing_tests = match fs::read_to_string(&tests_path) {
+		Ok(v) => Some(v),
+		Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+		Err(e) => return Err(e.into()),
+	};
+	let tests = puzzle::examples_test_module(&examples, existing_tests.as_deref());
+	let mut tests_file = File::create(&tests_path)?;
+	tests_file.write_all(tests.as_bytes())?;
+	let examples_enabled = ["src/lib.rs", "src/main.rs"].iter().any(|path| {
+		fs::read_to_string(path)
+			.map(|src| src.contains("mod examples;"))
+			.unwrap_or(false)
+	});
+	if !examples_enabled {
+		println!("Example tests generated in src/examples.rs, enable them with `#[cfg(test)] mod examples;`");
+	}
+
+	Ok(())
 }
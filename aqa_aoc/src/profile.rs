@@ -0,0 +1,220 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use thiserror::Error;
+use toml_edit::{Array, InlineTable, Item};
+
+/// Profile used by `new` and `fetch` when none is picked on the command line
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Dependencies of the built-in default profile, used when aoc_cfg.toml doesn't define one
+const BUILTIN_DEFAULT: &[(&str, &str)] =
+	&[("itertools", "0.9"), ("regex", "1.4"), ("nom", "7.0.0")];
+
+/// Dependencies by name, added to the `[dependencies]` of a new day crate
+pub type Profile = BTreeMap<String, Dependency>;
+
+/// Dependency of a profile, either a plain version or a table:
+///
+/// ```toml
+/// [profiles.math]
+/// num-bigint = "0.4"
+/// num-integer = { version = "0.1", default-features = false, features = ["i128"] }
+///
+/// [profiles.grid]
+/// grid = { path = "grid" }
+/// ```
+///
+/// Paths are relative to the workspace root. Only `version`, `path`, `features` and
+/// `default-features` are supported, other keys are rejected instead of being dropped.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged, deny_unknown_fields)]
+pub enum Dependency {
+	Version(String),
+	Detailed {
+		version: Option<String>,
+		path: Option<String>,
+		#[serde(default)]
+		features: Vec<String>,
+		#[serde(rename = "default-features")]
+		default_features: Option<bool>,
+	},
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ProfileError {
+	#[error("no dependency profile named \"{name}\", the available ones are: {available}")]
+	Unknown { name: String, available: String },
+	#[error("dependency \"{0}\" needs a version or a path")]
+	NoSource(String),
+}
+
+impl Dependency {
+	/// Renders the dependency for the manifest of a crate, `workspace_root` is the path of the
+	/// workspace root relative to the crate
+	pub fn to_item(&self, workspace_root: &str) -> Item {
+		let (version, path, features, default_features) = match self {
+			Dependency::Version(version) => return toml_edit::value(version.as_str()),
+			Dependency::Detailed {
+				version,
+				path,
+				features,
+				default_features,
+			} => (version, path, features, default_features),
+		};
+
+		let mut table = InlineTable::new();
+		if let Some(version) = version {
+			table.insert("version", version.as_str().into());
+		}
+		if let Some(path) = path {
+			table.insert("path", format!("{}/{}", workspace_root, path).into());
+		}
+		if let Some(default_features) = default_features {
+			table.insert("default-features", (*default_features).into());
+		}
+		if !features.is_empty() {
+			table.insert("features", features.iter().collect::<Array>().into());
+		}
+		toml_edit::value(table)
+	}
+}
+
+/// Merges the dependencies of the named profiles, later profiles override earlier ones.
+///
+/// No names select the `default` profile of the config, or the built-in one when it's not
+/// defined.
+pub fn resolve(
+	profiles: &BTreeMap<String, Profile>,
+	names: &[String],
+) -> Result<Profile, ProfileError> {
+	if names.is_empty() && !profiles.contains_key(DEFAULT_PROFILE) {
+		return Ok(BUILTIN_DEFAULT
+			.iter()
+			.map(|(name, version)| (name.to_string(), Dependency::Version(version.to_string())))
+			.collect());
+	}
+
+	let default_name = [DEFAULT_PROFILE.to_string()];
+	let names = if names.is_empty() {
+		&default_name[..]
+	} else {
+		names
+	};
+
+	let mut dependencies = Profile::new();
+	for name in names {
+		let profile = profiles.get(name).ok_or_else(|| ProfileError::Unknown {
+			name: name.clone(),
+			available: match profiles.keys().cloned().collect::<Vec<_>>() {
+				keys if keys.is_empty() => "none".to_string(),
+				keys => keys.join(", "),
+			},
+		})?;
+		for (dependency, spec) in profile {
+			if let Dependency::Detailed {
+				version: None,
+				path: None,
+				..
+			} = spec
+			{
+				return Err(ProfileError::NoSource(dependency.clone()));
+			}
+			dependencies.insert(dependency.clone(), spec.clone());
+		}
+	}
+
+	Ok(dependencies)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{resolve, Dependency, Profile, ProfileError};
+	use serde::Deserialize;
+	use std::collections::BTreeMap;
+
+	#[derive(Deserialize)]
+	struct Cfg {
+		profiles: BTreeMap<String, Profile>,
+	}
+
+	const PROFILES: &str = r#"
+[profiles.parsing]
+nom = "7.1"
+regex = "1.4"
+
+[profiles.grid]
+grid = { path = "grid" }
+
+[profiles.math]
+num = { version = "0.4", default-features = false, features = ["std", "rand"] }
+regex = "1.5"
+
+[profiles.broken]
+nothing = { features = ["a"] }
+"#;
+
+	fn names(names: &[&str]) -> Vec<String> {
+		names.iter().map(ToString::to_string).collect()
+	}
+
+	#[test]
+	fn profiles_are_merged() {
+		let profiles = toml::from_str::<Cfg>(PROFILES).unwrap().profiles;
+
+		let deps = resolve(&profiles, &names(&["parsing", "math"])).unwrap();
+		assert_eq!(deps.keys().collect::<Vec<_>>(), ["nom", "num", "regex"]);
+		assert_eq!(deps["regex"], Dependency::Version("1.5".to_string()));
+
+		assert!(matches!(
+			resolve(&profiles, &names(&["bigint"])),
+			Err(ProfileError::Unknown { available, .. }) if available == "broken, grid, math, parsing"
+		));
+		assert_eq!(
+			resolve(&profiles, &names(&["broken"])),
+			Err(ProfileError::NoSource("nothing".to_string()))
+		);
+	}
+
+	#[test]
+	fn default_profile() {
+		let builtin = resolve(&BTreeMap::new(), &[]).unwrap();
+		assert_eq!(
+			builtin.keys().collect::<Vec<_>>(),
+			["itertools", "nom", "regex"]
+		);
+
+		let mut profiles = toml::from_str::<Cfg>(PROFILES).unwrap().profiles;
+		let grid = profiles["grid"].clone();
+		profiles.insert("default".to_string(), grid);
+		assert_eq!(
+			resolve(&profiles, &[]).unwrap().keys().collect::<Vec<_>>(),
+			["grid"]
+		);
+	}
+
+	#[test]
+	fn dependencies_render() {
+		let profiles = toml::from_str::<Cfg>(PROFILES).unwrap().profiles;
+		let render = |profile: &str, name: &str| {
+			profiles[profile][name]
+				.to_item("../..")
+				.to_string()
+				.trim()
+				.to_string()
+		};
+
+		assert_eq!(render("parsing", "nom"), "\"7.1\"");
+		assert_eq!(render("grid", "grid"), "{ path = \"../../grid\" }");
+		assert_eq!(
+			render("math", "num"),
+			"{ version = \"0.4\", default-features = false, features = [\"std\", \"rand\"] }"
+		);
+	}
+
+	#[test]
+	fn unknown_keys_are_rejected() {
+		let profiles = "[profiles.grid]\ngrid = { path = \"grid\", package = \"aoc-grid\" }\n";
+		let err = toml::from_str::<Cfg>(profiles).err().unwrap();
+		assert!(err.to_string().contains("profiles.grid.grid"), "{}", err);
+	}
+}
@@ -0,0 +1,372 @@
+use ego_tree::NodeRef;
+use scraper::{ElementRef, Html, Node, Selector};
+use thiserror::Error;
+
+/// Example input found in a `<pre><code>` block of the puzzle description
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+	pub part: u32,
+	pub input: String,
+	/// Highlighted `<code><em>` answer that follows the example, if any
+	pub expected: Option<String>,
+}
+
+#[derive(Debug, Error)]
+pub enum PuzzleParseError {
+	#[error("no puzzle description found on the page")]
+	NoDescription,
+}
+
+/// Converts every `<article class="day-desc">` on a puzzle page into Markdown.
+///
+/// Relative links are resolved against `addr`.
+pub fn description_to_markdown(html: &str, addr: &str) -> Result<String, PuzzleParseError> {
+	let document = Html::parse_document(html);
+	// SAFETY: the selector is a valid constant
+	let selector = Selector::parse("article.day-desc").unwrap();
+
+	let articles = document
+		.select(&selector)
+		.map(|article| {
+			let mut md = String::new();
+			render_blocks(*article, addr, &mut md);
+			md
+		})
+		.collect::<Vec<_>>();
+
+	if articles.is_empty() {
+		return Err(PuzzleParseError::NoDescription);
+	}
+
+	Ok(articles.join("\n"))
+}
+
+/// Extracts the examples from every `<article class="day-desc">` on a puzzle page.
+///
+/// The expected answer of an example is the last highlighted `<code><em>` found before the
+/// next example.
+pub fn extract_examples(html: &str) -> Vec<Example> {
+	let document = Html::parse_document(html);
+	// SAFETY: the selectors are valid constants
+	let article_selector = Selector::parse("article.day-desc").unwrap();
+	let example_selector = Selector::parse("pre, code > em, em > code").unwrap();
+
+	let mut examples = Vec::new();
+	for (idx, article) in document.select(&article_selector).enumerate() {
+		let part = if idx == 0 { 1 } else { 2 };
+		let mut last_example = None;
+
+		for element in article.select(&example_selector) {
+			if element.value().name() == "pre" {
+				examples.push(Example {
+					part,
+					input: element.text().collect(),
+					expected: None,
+				});
+				last_example = Some(examples.len() - 1);
+				continue;
+			}
+
+			let in_pre = element
+				.ancestors()
+				.filter_map(ElementRef::wrap)
+				.any(|ancestor| ancestor.value().name() == "pre");
+			if in_pre {
+				continue;
+			}
+
+			if let Some(example) = last_example {
+				examples[example].expected = Some(element.text().collect());
+			}
+		}
+	}
+
+	examples
+}
+
+/// Generates the test module for the given examples.
+///
+/// Tests already present in `existing` are kept untouched, so the expected values filled in
+/// by hand survive a refresh.
+pub fn examples_test_module(examples: &[Example], existing: Option<&str>) -> String {
+	let mut module = match existing {
+		Some(existing) => existing.to_string(),
+		None => "//! Tests generated by aqa_aoc from the examples in the puzzle description\n\
+			\n\
+			#[allow(unused_imports)]\n\
+			use super::*;\n"
+			.to_string(),
+	};
+
+	for (idx, example) in examples.iter().enumerate() {
+		let test_name = format!("example_{}_part_{}", idx + 1, example.part);
+		if module.contains(&format!("fn {}(", test_name)) {
+			continue;
+		}
+
+		let expected = match &example.expected {
+			Some(expected) => format!("{:?}", expected),
+			None => "\"TODO\"".to_string(),
+		};
+		if example.expected.is_none() {
+			module.push_str("\n// TODO: fill in the expected answer");
+		}
+		module.push_str(&format!(
+			"\n#[test]\n\
+			fn {test_name}() {{\n\
+			\tlet input = include_str!(\"../examples/example_{idx}.txt\");\n\
+			\tassert_eq!(part_{part}(input).unwrap().to_string(), {expected});\n\
+			}}\n",
+			test_name = test_name,
+			idx = idx + 1,
+			part = example.part,
+			expected = expected,
+		));
+	}
+
+	module
+}
+
+fn render_blocks(node: NodeRef<Node>, addr: &str, md: &mut String) {
+	for child in node.children() {
+		let element = match ElementRef::wrap(child) {
+			Some(v) => v,
+			None => {
+				if let Node::Text(text) = child.value() {
+					if !text.trim().is_empty() {
+						md.push_str(text.trim());
+						md.push_str("\n\n");
+					}
+				}
+				continue;
+			}
+		};
+
+		match element.value().name() {
+			"h1" | "h2" | "h3" => {
+				md.push_str("## ");
+				render_inline(child, addr, md);
+				md.push_str("\n\n");
+			}
+			"p" => {
+				render_inline(child, addr, md);
+				md.push_str("\n\n");
+			}
+			"pre" => {
+				let code = element.text().collect::<String>();
+				md.push_str("```\n");
+				md.push_str(&code);
+				if !code.ends_with('\n') {
+					md.push('\n');
+				}
+				md.push_str("```\n\n");
+			}
+			"ul" | "ol" => {
+				for item in child.children().filter_map(ElementRef::wrap) {
+					md.push_str("- ");
+					render_inline(*item, addr, md);
+					md.push('\n');
+				}
+				md.push('\n');
+			}
+			_ => render_blocks(child, addr, md),
+		}
+	}
+}
+
+fn render_inline(node: NodeRef<Node>, addr: &str, md: &mut String) {
+	for child in node.children() {
+		let element = match ElementRef::wrap(child) {
+			Some(v) => v,
+			None => {
+				if let Node::Text(text) = child.value() {
+					md.push_str(&text.split('\n').collect::<Vec<_>>().join(" "));
+				}
+				continue;
+			}
+		};
+
+		match element.value().name() {
+			"code" => {
+				let code = element.text().collect::<String>();
+				let ticks = if code.contains('`') { "``" } else { "`" };
+				// Code spans can't hold emphasis, AoC uses `<code><em>` for the key values
+				let emphasis = element
+					.descendants()
+					.filter_map(ElementRef::wrap)
+					.any(|descendant| descendant.value().name() == "em");
+				if emphasis {
+					md.push('*');
+				}
+				md.push_str(ticks);
+				md.push_str(&code);
+				md.push_str(ticks);
+				if emphasis {
+					md.push('*');
+				}
+			}
+			"em" | "i" => {
+				md.push('*');
+				render_inline(child, addr, md);
+				md.push('*');
+			}
+			"strong" | "b" => {
+				md.push_str("**");
+				render_inline(child, addr, md);
+				md.push_str("**");
+			}
+			"a" => {
+				md.push('[');
+				render_inline(child, addr, md);
+				md.push_str("](");
+				match element.value().attr("href") {
+					Some(href) if href.starts_with('/') => {
+						md.push_str(addr);
+						md.push_str(href);
+					}
+					Some(href) => md.push_str(href),
+					None => (),
+				}
+				md.push(')');
+			}
+			"br" => md.push_str("  \n"),
+			_ => render_inline(child, addr, md),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{description_to_markdown, examples_test_module, extract_examples, Example};
+
+	const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Report Repair ---</h2><p>Before you leave, the Elves in accounting just need you to fix your <em>expense report</em> (your puzzle input).</p>
+<p>For example, suppose your expense report contained the following:</p>
+<pre><code>1721
+979
+</code></pre>
+<p>In this list, the two entries that sum to <code>2020</code> are <code>1721</code> and <code>299</code>. See <a href="/2020/about">about</a>.</p>
+<ul>
+<li>First <code><em>514579</em></code></li>
+<li>Second</li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>691771</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Find <em>three</em> numbers.</p></article>
+</main></body></html>"#;
+
+	#[test]
+	fn converts_both_parts() {
+		let md = description_to_markdown(PAGE, "https://adventofcode.com").unwrap();
+		assert_eq!(
+			md,
+			"## --- Day 1: Report Repair ---
+
+Before you leave, the Elves in accounting just need you to fix your *expense report* (your puzzle input).
+
+For example, suppose your expense report contained the following:
+
+```
+1721
+979
+```
+
+In this list, the two entries that sum to `2020` are `1721` and `299`. See [about](https://adventofcode.com/2020/about).
+
+- First *`514579`*
+- Second
+
+
+## --- Part Two ---
+
+Find *three* numbers.
+
+"
+		);
+	}
+
+	#[test]
+	fn finds_examples_with_answers() {
+		let page = r#"<article class="day-desc"><p>Example:</p>
+<pre><code>0,3,6
+</code></pre>
+<p>The answer is <code><em>436</em></code>.</p>
+<pre><code>1,<em>3</em>,2
+</code></pre>
+<p>No answer here.</p>
+</article>
+<article class="day-desc"><pre><code>3,1,2</code></pre><p>Then <code>1</code> becomes <code><em>362</em></code>.</p></article>"#;
+
+		assert_eq!(
+			extract_examples(page),
+			vec![
+				Example {
+					part: 1,
+					input: "0,3,6\n".to_string(),
+					expected: Some("436".to_string()),
+				},
+				Example {
+					part: 1,
+					input: "1,3,2\n".to_string(),
+					expected: None,
+				},
+				Example {
+					part: 2,
+					input: "3,1,2".to_string(),
+					expected: Some("362".to_string()),
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn test_module_keeps_existing_tests() {
+		let examples = vec![
+			Example {
+				part: 1,
+				input: String::new(),
+				expected: None,
+			},
+			Example {
+				part: 2,
+				input: String::new(),
+				expected: Some("362".to_string()),
+			},
+		];
+
+		let module = examples_test_module(&examples[..1], None);
+		assert_eq!(
+			module,
+			"//! Tests generated by aqa_aoc from the examples in the puzzle description
+
+#[allow(unused_imports)]
+use super::*;
+
+// TODO: fill in the expected answer
+#[test]
+fn example_1_part_1() {
+	let input = include_str!(\"../examples/example_1.txt\");
+	assert_eq!(part_1(input).unwrap().to_string(), \"TODO\");
+}
+"
+		);
+
+		let edited = module.replace("\"TODO\"", "\"436\"");
+		let module = examples_test_module(&examples, Some(&edited));
+		assert!(module.starts_with(&edited));
+		assert!(module.ends_with(
+			"
+#[test]
+fn example_2_part_2() {
+	let input = include_str!(\"../examples/example_2.txt\");
+	assert_eq!(part_2(input).unwrap().to_string(), \"362\");
+}
+"
+		));
+	}
+
+	#[test]
+	fn missing_description() {
+		assert!(description_to_markdown("<html></html>", "").is_err());
+	}
+}
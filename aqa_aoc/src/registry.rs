@@ -0,0 +1,72 @@
+use thiserror::Error;
+
+/// Marker line above which new days are registered
+const REGISTRY_MARKER: &str = "// New days are registered above this line by aqa_aoc";
+
+#[derive(Debug, Error)]
+pub enum RegistryError {
+	#[error("registry marker not found")]
+	MissingMarker,
+	#[error("day {day} of {year} is already registered")]
+	AlreadyRegistered { year: u32, day: u32 },
+}
+
+/// Adds an entry for the given day crate to the source of the `aoc_runner` registry
+pub fn register(
+	registry_src: &str,
+	year: u32,
+	day: u32,
+	crate_name: &str,
+	member: &str,
+) -> Result<String, RegistryError> {
+	if registry_src.contains(&format!("\t({}, {}, \"", year, day)) {
+		return Err(RegistryError::AlreadyRegistered { year, day });
+	}
+
+	let marker = registry_src
+		.find(&format!("\t{}", REGISTRY_MARKER))
+		.ok_or(RegistryError::MissingMarker)?;
+
+	let mut src = registry_src.to_string();
+	src.insert_str(
+		marker,
+		&format!(
+			"\t({year}, {day}, {member:?}, run::<{crate_name}::Day{day}>),\n",
+			year = year,
+			day = day,
+			member = member,
+			crate_name = crate_name
+		),
+	);
+	Ok(src)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{register, RegistryError};
+
+	const REGISTRY: &str = "pub const SOLUTIONS: &[(u32, u32, &str, Runner)] = &[
+	(2020, 1, \"day_1\", run::<day_1::Day1>),
+	// New days are registered above this line by aqa_aoc
+];
+";
+
+	#[test]
+	fn new_day_is_registered() {
+		let registered = register(REGISTRY, 2020, 2, "day_2", "day_2").unwrap();
+		assert!(registered.contains(
+			"\t(2020, 2, \"day_2\", run::<day_2::Day2>),\n\t// New days are registered above this line by aqa_aoc\n"
+		));
+
+		assert!(matches!(
+			register(&registered, 2020, 2, "day_2", "day_2"),
+			Err(RegistryError::AlreadyRegistered { year: 2020, day: 2 })
+		));
+		let registered = register(&registered, 2021, 2, "y2021_day_2", "y2021/day_2").unwrap();
+		assert!(registered.contains("\t(2021, 2, \"y2021/day_2\", run::<y2021_day_2::Day2>),\n"));
+		assert!(matches!(
+			register("", 2020, 2, "day_2", "day_2"),
+			Err(RegistryError::MissingMarker)
+		));
+	}
+}
@@ -0,0 +1,209 @@
+use crate::answers::Answers;
+use crate::workspace::DayCrate;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// State of a single day of the event in the workspace
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct DayStatus {
+	pub day: u32,
+	/// Whether the day crate is a workspace member
+	pub member: bool,
+	pub input: bool,
+	pub readme: bool,
+	/// Number of files in the `examples` directory of the crate
+	pub examples: usize,
+	/// Answers recorded in the answers ledger
+	pub answers: [Option<String>; 2],
+	/// Stars shown on the calendar, `None` when it wasn't fetched
+	pub stars: Option<u32>,
+}
+
+/// Collects the status of days 1-25 of `year`, crate paths are relative to `root`
+pub fn collect(
+	root: &Path,
+	year: u32,
+	day_crates: &[DayCrate],
+	answers: &Answers,
+	stars: Option<&BTreeMap<u32, u32>>,
+) -> Vec<DayStatus> {
+	(1..=25)
+		.map(|day| {
+			let mut status = DayStatus {
+				day,
+				answers: [1, 2].map(|part| answers.get(year, day, part)),
+				stars: stars.map(|stars| stars.get(&day).copied().unwrap_or(0)),
+				..DayStatus::default()
+			};
+
+			let day_crate = match day_crates.iter().find(|day_crate| day_crate.day == day) {
+				Some(v) => v,
+				None => return status,
+			};
+			let crate_dir = root.join(day_crate.member());
+			status.member = true;
+			status.input = crate_dir.join("input.txt").is_file();
+			status.readme = crate_dir.join("README.md").is_file();
+			status.examples = fs::read_dir(crate_dir.join("examples"))
+				.map(|examples| examples.filter_map(Result::ok).count())
+				.unwrap_or(0);
+
+			status
+		})
+		.collect()
+}
+
+/// Renders the statuses as a table, the stars column is left out when none were fetched
+pub fn render(statuses: &[DayStatus]) -> String {
+	let with_stars = statuses.iter().any(|status| status.stars.is_some());
+	let flag = |set: bool| if set { "yes" } else { "-" };
+
+	let mut rows = vec![[
+		"Day", "Member", "Input", "README", "Examples", "Stars", "Part 1", "Part 2",
+	]
+	.iter()
+	.map(ToString::to_string)
+	.collect::<Vec<_>>()];
+	for status in statuses {
+		rows.push(vec![
+			status.day.to_string(),
+			flag(status.member).to_string(),
+			flag(status.input).to_string(),
+			flag(status.readme).to_string(),
+			match status.examples {
+				0 => "-".to_string(),
+				examples => examples.to_string(),
+			},
+			"*".repeat(status.stars.unwrap_or(0) as usize),
+			status.answers[0].clone().unwrap_or_else(|| "-".to_string()),
+			status.answers[1].clone().unwrap_or_else(|| "-".to_string()),
+		]);
+	}
+	if !with_stars {
+		for row in &mut rows {
+			row.remove(5);
+		}
+	}
+
+	let columns = rows[0].len();
+	let widths = (0..columns)
+		.map(|column| rows.iter().map(|row| row[column].len()).max().unwrap_or(0))
+		.collect::<Vec<_>>();
+
+	let mut table = String::new();
+	for row in rows {
+		let line = row
+			.iter()
+			.zip(&widths)
+			.enumerate()
+			.map(|(column, (cell, width))| match column {
+				0 => format!("{:>1$}", cell, width),
+				_ => format!("{:1$}", cell, width),
+			})
+			.collect::<Vec<_>>()
+			.join("  ");
+		table.push_str(line.trim_end());
+		table.push('\n');
+	}
+
+	table
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{collect, render, DayStatus};
+	use crate::answers::Answers;
+	use crate::workspace::DayCrate;
+	use std::collections::BTreeMap;
+	use std::fs;
+
+	#[test]
+	fn collects_files_and_answers() {
+		let dir = tempfile::tempdir().unwrap();
+		let day_1 = dir.path().join("day_1");
+		fs::create_dir_all(day_1.join("examples")).unwrap();
+		fs::write(day_1.join("input.txt"), "1\n").unwrap();
+		fs::write(day_1.join("README.md"), "").unwrap();
+		fs::write(day_1.join("examples").join("example_1.txt"), "").unwrap();
+		fs::write(day_1.join("examples").join("example_2.txt"), "").unwrap();
+		fs::create_dir_all(dir.path().join("day_2")).unwrap();
+
+		let day_crates = [1, 2]
+			.iter()
+			.map(|day| DayCrate {
+				year: 2020,
+				day: *day,
+				nested: false,
+			})
+			.collect::<Vec<_>>();
+		let answers = Answers::parse("[2020.day_1]\npart_1 = \"514579\"\n").unwrap();
+		let stars = [(1, 1), (2, 0)].iter().copied().collect::<BTreeMap<_, _>>();
+
+		let statuses = collect(dir.path(), 2020, &day_crates, &answers, Some(&stars));
+		assert_eq!(statuses.len(), 25);
+		assert_eq!(
+			statuses[0],
+			DayStatus {
+				day: 1,
+				member: true,
+				input: true,
+				readme: true,
+				examples: 2,
+				answers: [Some("514579".to_string()), None],
+				stars: Some(1),
+			}
+		);
+		assert_eq!(
+			statuses[1],
+			DayStatus {
+				day: 2,
+				member: true,
+				stars: Some(0),
+				..DayStatus::default()
+			}
+		);
+		assert!(!statuses[2].member);
+
+		let offline = collect(dir.path(), 2020, &day_crates, &answers, None);
+		assert_eq!(offline[0].stars, None);
+	}
+
+	#[test]
+	fn renders_table() {
+		let statuses = vec![
+			DayStatus {
+				day: 1,
+				member: true,
+				input: true,
+				examples: 2,
+				answers: [Some("514579".to_string()), None],
+				stars: Some(1),
+				..DayStatus::default()
+			},
+			DayStatus {
+				day: 10,
+				stars: Some(0),
+				..DayStatus::default()
+			},
+		];
+		assert_eq!(
+			render(&statuses),
+			"Day  Member  Input  README  Examples  Stars  Part 1  Part 2
+  1  yes     yes    -       2         *      514579  -
+ 10  -       -      -       -                -       -
+"
+		);
+
+		let offline = statuses
+			.into_iter()
+			.map(|status| DayStatus {
+				stars: None,
+				..status
+			})
+			.collect::<Vec<_>>();
+		assert!(
+			render(&offline).starts_with("Day  Member  Input  README  Examples  Part 1  Part 2\n")
+		);
+	}
+}
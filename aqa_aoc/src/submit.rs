@@ -0,0 +1,195 @@
+use std::fmt;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Verdict the site gave for a submitted answer
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SubmissionResult {
+	Correct,
+	Wrong,
+	TooHigh,
+	TooLow,
+	AlreadySolved,
+	RateLimited { wait: Duration },
+}
+
+#[derive(Debug, Error)]
+pub enum SubmissionParseError {
+	#[error("no <article> found in the response")]
+	NoArticle,
+	#[error("unrecognized response: {0}")]
+	Unrecognized(String),
+}
+
+impl SubmissionResult {
+	/// Exit code reported by `aqa_aoc submit`, distinct for every verdict
+	pub fn exit_code(&self) -> i32 {
+		match self {
+			SubmissionResult::Correct => 0,
+			SubmissionResult::Wrong => 10,
+			SubmissionResult::TooHigh => 11,
+			SubmissionResult::TooLow => 12,
+			SubmissionResult::AlreadySolved => 13,
+			SubmissionResult::RateLimited { .. } => 14,
+		}
+	}
+
+	pub fn from_html(html: &str) -> Result<Self, SubmissionParseError> {
+		let article = article_text(html).ok_or(SubmissionParseError::NoArticle)?;
+
+		if article.contains("That's the right answer") {
+			Ok(SubmissionResult::Correct)
+		} else if article.contains("You gave an answer too recently") {
+			Ok(SubmissionResult::RateLimited {
+				wait: parse_wait_time(&article).unwrap_or_default(),
+			})
+		} else if article.contains("Did you already complete it") {
+			Ok(SubmissionResult::AlreadySolved)
+		} else if article.contains("That's not the right answer") {
+			if article.contains("your answer is too high") {
+				Ok(SubmissionResult::TooHigh)
+			} else if article.contains("your answer is too low") {
+				Ok(SubmissionResult::TooLow)
+			} else {
+				Ok(SubmissionResult::Wrong)
+			}
+		} else {
+			Err(SubmissionParseError::Unrecognized(article))
+		}
+	}
+}
+
+impl fmt::Display for SubmissionResult {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			SubmissionResult::Correct => write!(f, "correct"),
+			SubmissionResult::Wrong => write!(f, "wrong"),
+			SubmissionResult::TooHigh => write!(f, "wrong (too high)"),
+			SubmissionResult::TooLow => write!(f, "wrong (too low)"),
+			SubmissionResult::AlreadySolved => write!(f, "already solved"),
+			SubmissionResult::RateLimited { wait } => {
+				write!(f, "rate limited, {}s left to wait", wait.as_secs())
+			}
+		}
+	}
+}
+
+/// Returns the text of the first `<article>` with tags stripped and whitespace collapsed
+fn article_text(html: &str) -> Option<String> {
+	let start = html.find("<article")?;
+	let start = start + html[start..].find('>')? + 1;
+	let end = start + html[start..].find("</article>")?;
+
+	let mut text = String::new();
+	let mut in_tag = false;
+	for c in html[start..end].chars() {
+		match c {
+			'<' => in_tag = true,
+			'>' => in_tag = false,
+			c if !in_tag => text.push(c),
+			_ => (),
+		}
+	}
+
+	Some(text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// Parses "You have 1m 4s left to wait" style messages
+fn parse_wait_time(article: &str) -> Option<Duration> {
+	let start = article.find("You have ")? + "You have ".len();
+	let end = start + article[start..].find(" left to wait")?;
+
+	let mut secs = 0;
+	for part in article[start..end].split_whitespace() {
+		let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+		let value = value.parse::<u64>().ok()?;
+		secs += match unit {
+			"h" => value * 3600,
+			"m" => value * 60,
+			"s" => value,
+			_ => return None,
+		};
+	}
+
+	Some(Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::SubmissionResult;
+	use std::time::Duration;
+
+	fn page(article: &str) -> String {
+		format!(
+			"<html><body><main><article><p>{}</p></article></main></body></html>",
+			article
+		)
+	}
+
+	#[test]
+	fn correct() {
+		let html = page("That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to saving your vacation.");
+		assert_eq!(
+			SubmissionResult::from_html(&html).unwrap(),
+			SubmissionResult::Correct
+		);
+	}
+
+	#[test]
+	fn wrong() {
+		let html = page("That's not the right answer.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again.");
+		assert_eq!(
+			SubmissionResult::from_html(&html).unwrap(),
+			SubmissionResult::Wrong
+		);
+	}
+
+	#[test]
+	fn too_high_and_too_low() {
+		let html = page("That's not the right answer; your answer is too high.  Please wait one minute before trying again.");
+		assert_eq!(
+			SubmissionResult::from_html(&html).unwrap(),
+			SubmissionResult::TooHigh
+		);
+
+		let html = page("That's not the right answer; your answer is too low.  Please wait one minute before trying again.");
+		assert_eq!(
+			SubmissionResult::from_html(&html).unwrap(),
+			SubmissionResult::TooLow
+		);
+	}
+
+	#[test]
+	fn already_solved() {
+		let html = page("You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2020/day/1\">[Return to Day 1]</a>");
+		assert_eq!(
+			SubmissionResult::from_html(&html).unwrap(),
+			SubmissionResult::AlreadySolved
+		);
+	}
+
+	#[test]
+	fn rate_limited() {
+		let html = page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 4s left to wait.");
+		assert_eq!(
+			SubmissionResult::from_html(&html).unwrap(),
+			SubmissionResult::RateLimited {
+				wait: Duration::from_secs(64)
+			}
+		);
+
+		let html = page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 38s left to wait.");
+		assert_eq!(
+			SubmissionResult::from_html(&html).unwrap(),
+			SubmissionResult::RateLimited {
+				wait: Duration::from_secs(38)
+			}
+		);
+	}
+
+	#[test]
+	fn unrecognized() {
+		assert!(SubmissionResult::from_html("<html></html>").is_err());
+		assert!(SubmissionResult::from_html(&page("Something else")).is_err());
+	}
+}
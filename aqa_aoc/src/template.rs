@@ -0,0 +1,183 @@
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Template file names and the paths they are rendered to, relative to the new crate
+pub const TEMPLATE_FILES: &[(&str, &str)] = &[
+	("Cargo.toml.tmpl", "Cargo.toml"),
+	("main.rs.tmpl", "src/main.rs"),
+	("lib.rs.tmpl", "src/lib.rs"),
+	("tests.rs.tmpl", "src/tests.rs"),
+];
+
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+	(
+		"Cargo.toml.tmpl",
+		include_str!("../templates/Cargo.toml.tmpl"),
+	),
+	("main.rs.tmpl", include_str!("../templates/main.rs.tmpl")),
+	("lib.rs.tmpl", include_str!("../templates/lib.rs.tmpl")),
+	("tests.rs.tmpl", include_str!("../templates/tests.rs.tmpl")),
+];
+
+#[derive(Debug, Error)]
+pub enum TemplateError {
+	#[error("failed to read template {0}")]
+	Read(PathBuf, #[source] io::Error),
+	#[error("no template named {0}")]
+	Missing(String),
+	#[error("unknown placeholder {{{{{placeholder}}}}} in {template}")]
+	UnknownPlaceholder {
+		template: String,
+		placeholder: String,
+	},
+	#[error("unterminated placeholder in {0}")]
+	Unterminated(String),
+}
+
+/// Values substituted for `{{day}}`, `{{year}}`, `{{crate_name}}`, `{{crate_path}}` and
+/// `{{workspace_root}}`
+pub struct TemplateVars<'a> {
+	pub day: u32,
+	pub year: u32,
+	pub crate_name: &'a str,
+	/// Crate directory relative to the workspace root
+	pub crate_path: &'a str,
+	/// Workspace root relative to the crate directory
+	pub workspace_root: &'a str,
+}
+
+/// Day crate templates, files in the user directory take precedence over the built-in ones
+pub struct Templates {
+	user_dir: Option<PathBuf>,
+}
+
+impl Templates {
+	pub fn new(user_dir: Option<&Path>) -> Templates {
+		Templates {
+			user_dir: user_dir.map(ToOwned::to_owned),
+		}
+	}
+
+	fn source(&self, name: &str) -> Result<String, TemplateError> {
+		if let Some(dir) = &self.user_dir {
+			let path = dir.join(name);
+			match fs::read_to_string(&path) {
+				Ok(v) => return Ok(v),
+				Err(e) if e.kind() == ErrorKind::NotFound => (),
+				Err(e) => return Err(TemplateError::Read(path, e)),
+			}
+		}
+
+		BUILTIN_TEMPLATES
+			.iter()
+			.find(|(builtin_name, _)| *builtin_name == name)
+			.map(|(_, source)| source.to_string())
+			.ok_or_else(|| TemplateError::Missing(name.to_string()))
+	}
+
+	pub fn render(&self, name: &str, vars: &TemplateVars) -> Result<String, TemplateError> {
+		render(name, &self.source(name)?, vars)
+	}
+}
+
+fn render(name: &str, source: &str, vars: &TemplateVars) -> Result<String, TemplateError> {
+	let mut rendered = String::with_capacity(source.len());
+	let mut rest = source;
+
+	while let Some(start) = rest.find("{{") {
+		rendered.push_str(&rest[..start]);
+		let end = rest[start..]
+			.find("}}")
+			.ok_or_else(|| TemplateError::Unterminated(name.to_string()))?;
+
+		let placeholder = rest[start + 2..start + end].trim();
+		match placeholder {
+			"day" => rendered.push_str(&vars.day.to_string()),
+			"year" => rendered.push_str(&vars.year.to_string()),
+			"crate_name" => rendered.push_str(vars.crate_name),
+			"crate_path" => rendered.push_str(vars.crate_path),
+			"workspace_root" => rendered.push_str(vars.workspace_root),
+			_ => {
+				return Err(TemplateError::UnknownPlaceholder {
+					template: name.to_string(),
+					placeholder: placeholder.to_string(),
+				})
+			}
+		}
+
+		rest = &rest[start + end + 2..];
+	}
+	rendered.push_str(rest);
+
+	Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{render, TemplateError, TemplateVars, Templates, TEMPLATE_FILES};
+
+	const VARS: TemplateVars = TemplateVars {
+		day: 19,
+		year: 2020,
+		crate_name: "day_19",
+		crate_path: "day_19",
+		workspace_root: "..",
+	};
+
+	#[test]
+	fn placeholders() {
+		assert_eq!(
+			render("t", "{{crate_name}}: {{ year }}/{{day}} {}", &VARS).unwrap(),
+			"day_19: 2020/19 {}"
+		);
+		assert!(matches!(
+			render("t", "{{month}}", &VARS),
+			Err(TemplateError::UnknownPlaceholder { placeholder, .. }) if placeholder == "month"
+		));
+		assert!(matches!(
+			render("t", "{{day", &VARS),
+			Err(TemplateError::Unterminated(_))
+		));
+	}
+
+	#[test]
+	fn builtin_templates_render() {
+		let templates = Templates::new(None);
+		for (name, _) in TEMPLATE_FILES {
+			let rendered = templates.render(name, &VARS).unwrap();
+			assert!(!rendered.contains("{{"), "{}", name);
+		}
+
+		let main = templates.render("main.rs.tmpl", &VARS).unwrap();
+		assert!(main.contains("main::<day_19::Day19>(env!(\"CARGO_MANIFEST_DIR\"))"));
+
+		let nested = TemplateVars {
+			crate_name: "y2021_day_19",
+			crate_path: "y2021/day_19",
+			workspace_root: "../..",
+			..VARS
+		};
+		let main = templates.render("main.rs.tmpl", &nested).unwrap();
+		assert!(main.contains("main::<y2021_day_19::Day19>(env!(\"CARGO_MANIFEST_DIR\"))"));
+		let manifest = templates.render("Cargo.toml.tmpl", &nested).unwrap();
+		assert!(manifest.contains("path = \"../../aoc_solution\""));
+	}
+
+	#[test]
+	fn user_templates_take_precedence() {
+		let dir = tempfile::tempdir().unwrap();
+		std::fs::write(dir.path().join("main.rs.tmpl"), "// day {{day}}\n").unwrap();
+
+		let templates = Templates::new(Some(dir.path()));
+		assert_eq!(
+			templates.render("main.rs.tmpl", &VARS).unwrap(),
+			"// day 19\n"
+		);
+		assert!(templates
+			.render("lib.rs.tmpl", &VARS)
+			.unwrap()
+			.contains("pub fn part_1"));
+	}
+}
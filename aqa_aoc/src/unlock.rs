@@ -0,0 +1,179 @@
+use crate::client::AocError;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Puzzles unlock at midnight US Eastern time, which is UTC-5 in December
+const UNLOCK_OFFSET: Duration = Duration::from_secs(5 * 3600);
+
+/// Source of time, replaced in tests to avoid waiting
+pub trait Clock {
+	fn now(&self) -> SystemTime;
+	fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+	fn now(&self) -> SystemTime {
+		SystemTime::now()
+	}
+
+	fn sleep(&self, duration: Duration) {
+		thread::sleep(duration)
+	}
+}
+
+/// Returns the instant the puzzle of the given December day unlocks
+pub fn unlock_time(year: u32, day: u32) -> SystemTime {
+	UNIX_EPOCH + Duration::from_secs(days_from_civil(year, 12, day) * 86400) + UNLOCK_OFFSET
+}
+
+/// Days between 1970-01-01 and the given date of the Gregorian calendar
+fn days_from_civil(year: u32, month: u32, day: u32) -> u64 {
+	// Years start in March so the leap day is the last day of a year
+	let year = if month <= 2 { year - 1 } else { year } as u64;
+	let era = year / 400;
+	let year_of_era = year % 400;
+	let month = month as u64;
+	let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day as u64 - 1;
+	let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+	era * 146_097 + day_of_era - 719_468
+}
+
+/// First day of the event that is still locked at `now`
+pub fn next_unlock(year: u32, now: SystemTime) -> Option<u32> {
+	(1..=25).find(|day| unlock_time(year, *day) > now)
+}
+
+/// Sleeps until `instant`, `on_tick` gets the remaining time about once a second
+pub fn wait_until(clock: &dyn Clock, instant: SystemTime, mut on_tick: impl FnMut(Duration)) {
+	loop {
+		let remaining = match instant.duration_since(clock.now()) {
+			Ok(v) if v > Duration::from_secs(0) => v,
+			_ => return,
+		};
+		on_tick(remaining);
+		clock.sleep(remaining.min(Duration::from_secs(1)));
+	}
+}
+
+/// Formats the time left as `hh:mm:ss`, prefixed with the number of days when needed
+pub fn format_countdown(remaining: Duration) -> String {
+	// Rounded up so the countdown doesn't show zero before the unlock
+	let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+	let time = format!(
+		"{:02}:{:02}:{:02}",
+		secs % 86400 / 3600,
+		secs % 3600 / 60,
+		secs % 60
+	);
+	match secs / 86400 {
+		0 => time,
+		days => format!("{}d {}", days, time),
+	}
+}
+
+/// Delays between retries, doubled after every attempt up to `max`
+#[derive(Debug, Clone)]
+pub struct Backoff {
+	pub initial: Duration,
+	pub max: Duration,
+	pub attempts: u32,
+}
+
+impl Backoff {
+	/// Default backoff that never retries sooner than `interval`, the throttle would make
+	/// shorter delays pointless
+	pub fn at_least(interval: Duration) -> Backoff {
+		let default = Backoff::default();
+		Backoff {
+			initial: default.initial.max(interval),
+			max: default.max.max(interval),
+			..default
+		}
+	}
+}
+
+impl Default for Backoff {
+	fn default() -> Backoff {
+		Backoff {
+			initial: Duration::from_secs(1),
+			max: Duration::from_secs(8),
+			attempts: 10,
+		}
+	}
+}
+
+/// Retries `request` while it fails with [AocError::NotFound], the input of a day can be
+/// missing for a few seconds after the unlock
+pub fn retry_not_found<T>(
+	clock: &dyn Clock,
+	backoff: &Backoff,
+	mut request: impl FnMut() -> Result<T, AocError>,
+) -> Result<T, AocError> {
+	let mut delay = backoff.initial;
+	for _ in 1..backoff.attempts {
+		match request() {
+			Err(AocError::NotFound(url)) => {
+				println!("{} not found yet, retrying in {:?}", url, delay);
+				clock.sleep(delay);
+				delay = (delay * 2).min(backoff.max);
+			}
+			result => return result,
+		}
+	}
+	request()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{format_countdown, next_unlock, unlock_time, Backoff};
+	use std::time::{Duration, UNIX_EPOCH};
+
+	#[test]
+	fn unlock_instants() {
+		// 2020-12-01T05:00:00Z
+		assert_eq!(
+			unlock_time(2020, 1),
+			UNIX_EPOCH + Duration::from_secs(1_606_798_800)
+		);
+		// 2023-12-25T05:00:00Z
+		assert_eq!(
+			unlock_time(2023, 25),
+			UNIX_EPOCH + Duration::from_secs(1_703_480_400)
+		);
+
+		let before_day_3 = unlock_time(2020, 3) - Duration::from_secs(1);
+		assert_eq!(next_unlock(2020, before_day_3), Some(3));
+		assert_eq!(next_unlock(2020, unlock_time(2020, 3)), Some(4));
+		assert_eq!(next_unlock(2020, unlock_time(2020, 25)), None);
+	}
+
+	#[test]
+	fn countdown_format() {
+		assert_eq!(format_countdown(Duration::from_secs(3723)), "01:02:03");
+		assert_eq!(
+			format_countdown(Duration::from_secs(2 * 86400 + 59)),
+			"2d 00:00:59"
+		);
+	}
+
+	#[test]
+	fn backoff_respects_the_interval() {
+		let backoff = Backoff::at_least(Duration::from_secs(5));
+		assert_eq!(
+			(backoff.initial, backoff.max),
+			(Duration::from_secs(5), Duration::from_secs(8))
+		);
+		let backoff = Backoff::at_least(Duration::from_secs(30));
+		assert_eq!(
+			(backoff.initial, backoff.max),
+			(Duration::from_secs(30), Duration::from_secs(30))
+		);
+		assert_eq!(
+			Backoff::at_least(Duration::from_secs(0)).initial,
+			Backoff::default().initial
+		);
+	}
+}
@@ -0,0 +1,518 @@
+use crate::unlock::Clock;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+/// How often the watched files and the running command are checked
+pub const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Quiet time after a change before a run starts, editors often write a file several times
+pub const DEBOUNCE: Duration = Duration::from_millis(400);
+
+/// Modification time and size of every file under the watched paths
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Snapshot(BTreeMap<PathBuf, (Option<SystemTime>, u64)>);
+
+impl Snapshot {
+	/// Walks the paths, missing ones are skipped so they can be created later
+	pub fn take(paths: &[PathBuf]) -> Snapshot {
+		let mut snapshot = Snapshot::default();
+		for path in paths {
+			snapshot.add(path);
+		}
+		snapshot
+	}
+
+	fn add(&mut self, path: &Path) {
+		let metadata = match fs::metadata(path) {
+			Ok(v) => v,
+			Err(_) => return,
+		};
+		if metadata.is_dir() {
+			if let Ok(entries) = fs::read_dir(path) {
+				for entry in entries.filter_map(Result::ok) {
+					self.add(&entry.path());
+				}
+			}
+		} else {
+			self.0
+				.insert(path.to_owned(), (metadata.modified().ok(), metadata.len()));
+		}
+	}
+}
+
+/// Polls the watched paths for changes
+pub struct Watcher {
+	paths: Vec<PathBuf>,
+	snapshot: Snapshot,
+}
+
+impl Watcher {
+	pub fn new(paths: Vec<PathBuf>) -> Watcher {
+		let snapshot = Snapshot::take(&paths);
+		Watcher { paths, snapshot }
+	}
+
+	/// Whether anything changed since the previous call
+	pub fn changed(&mut self) -> bool {
+		let snapshot = Snapshot::take(&self.paths);
+		if snapshot == self.snapshot {
+			return false;
+		}
+		self.snapshot = snapshot;
+		true
+	}
+
+	/// Waits until nothing changed for [DEBOUNCE]
+	pub fn settle(&mut self, clock: &dyn Clock) {
+		let mut quiet = Duration::from_secs(0);
+		while quiet < DEBOUNCE {
+			clock.sleep(POLL_INTERVAL);
+			quiet = if self.changed() {
+				Duration::from_secs(0)
+			} else {
+				quiet + POLL_INTERVAL
+			};
+		}
+	}
+
+	/// Blocks until a burst of changes is over
+	pub fn wait_for_change(&mut self, clock: &dyn Clock) {
+		while !self.changed() {
+			clock.sleep(POLL_INTERVAL);
+		}
+		self.settle(clock);
+	}
+}
+
+/// Line printed by a command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+	pub text: String,
+}
+
+/// Output of a command that ran to completion
+#[derive(Debug)]
+pub struct Finished {
+	pub success: bool,
+	pub elapsed: Duration,
+	/// stdout and stderr, interleaved in the order they were read
+	pub lines: Vec<Line>,
+}
+
+/// Runs `command`, killing it as soon as `cancel` returns true.
+///
+/// `cancel` is polled every [POLL_INTERVAL] while the command runs, returns `None` when the
+/// command was cancelled.
+pub fn run_cancellable(
+	command: &mut Command,
+	mut cancel: impl FnMut() -> bool,
+) -> io::Result<Option<Finished>> {
+	let start = Instant::now();
+	let mut child = command
+		.stdin(Stdio::null())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.spawn()?;
+
+	let (sender, receiver) = mpsc::channel();
+	// Both streams are piped above
+	let readers = [
+		spawn_reader(child.stdout.take().unwrap(), sender.clone()),
+		spawn_reader(child.stderr.take().unwrap(), sender),
+	];
+
+	let mut last_poll = start;
+	let status = loop {
+		if let Some(status) = child.try_wait()? {
+			break status;
+		}
+		if last_poll.elapsed() >= POLL_INTERVAL {
+			last_poll = Instant::now();
+			if cancel() {
+				kill_tree(&mut child)?;
+				return Ok(None);
+			}
+		}
+		// Short sleeps so the time of fast commands isn't rounded up to the poll interval
+		thread::sleep(Duration::from_millis(10));
+	};
+	let elapsed = start.elapsed();
+
+	for reader in readers {
+		let _ = reader.join();
+	}
+	Ok(Some(Finished {
+		success: status.success(),
+		elapsed,
+		lines: receiver.into_iter().collect(),
+	}))
+}
+
+/// Kills the child with the processes it started, like the test binaries of `cargo test`.
+///
+/// The children stay in the process group of aqa_aoc so Ctrl-C still reaches them.
+fn kill_tree(child: &mut Child) -> io::Result<()> {
+	#[cfg(unix)]
+	for pid in descendants(child.id()) {
+		let _ = Command::new("kill")
+			.args(["-KILL", &pid.to_string()])
+			.stderr(Stdio::null())
+			.status();
+	}
+	#[cfg(windows)]
+	let _ = Command::new("taskkill")
+		.args(["/T", "/F", "/PID", &child.id().to_string()])
+		.stdout(Stdio::null())
+		.stderr(Stdio::null())
+		.status();
+
+	child.kill()?;
+	child.wait()?;
+	Ok(())
+}
+
+/// Children of `pid` and their own children, they have to be listed while `pid` is alive
+#[cfg(unix)]
+fn descendants(pid: u32) -> Vec<u32> {
+	let output = match Command::new("pgrep")
+		.args(["-P", &pid.to_string()])
+		.output()
+	{
+		Ok(v) => v,
+		Err(_) => return Vec::new(),
+	};
+	String::from_utf8_lossy(&output.stdout)
+		.split_whitespace()
+		.filter_map(|pid| pid.parse().ok())
+		.flat_map(|pid| {
+			let mut pids = vec![pid];
+			pids.extend(descendants(pid));
+			pids
+		})
+		.collect()
+}
+
+fn spawn_reader(
+	stream: impl Read + Send + 'static,
+	sender: mpsc::Sender<Line>,
+) -> thread::JoinHandle<()> {
+	thread::spawn(move || {
+		for text in BufReader::new(stream).lines().map_while(Result::ok) {
+			if sender.send(Line { text }).is_err() {
+				return;
+			}
+		}
+	})
+}
+
+/// Answer printed by a day binary, with the time it measured for the part alone
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartAnswer {
+	pub part: u32,
+	pub answer: String,
+	pub time: Duration,
+}
+
+/// Picks the `Part N: answer (time)` lines out of the output of a day binary
+pub fn part_answers(lines: &[Line]) -> Vec<PartAnswer> {
+	lines
+		.iter()
+		.filter_map(|line| {
+			let rest = line.text.strip_prefix("Part ")?;
+			let (part, rest) = rest.split_once(": ")?;
+			let (answer, time) = rest.strip_suffix(')')?.rsplit_once(" (")?;
+			Some(PartAnswer {
+				part: part.parse().ok()?,
+				answer: answer.to_string(),
+				time: parse_duration(time)?,
+			})
+		})
+		.collect()
+}
+
+/// Parses a duration in the `Debug` format of [Duration], like `1.5ms`
+fn parse_duration(text: &str) -> Option<Duration> {
+	let unit_start = text.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+	let (value, unit) = text.split_at(unit_start);
+	let unit_nanos: u64 = match unit {
+		"s" => 1_000_000_000,
+		"ms" => 1_000_000,
+		"µs" => 1_000,
+		"ns" => 1,
+		_ => return None,
+	};
+
+	let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+	let mut nanos = whole.parse::<u64>().ok()?.checked_mul(unit_nanos)?;
+	let mut scale = unit_nanos;
+	for digit in fraction.chars() {
+		scale /= 10;
+		nanos += u64::from(digit.to_digit(10)?) * scale;
+	}
+	Some(Duration::from_nanos(nanos))
+}
+
+/// Sums the passed and failed counts of the `test result:` lines of `cargo test`
+pub fn test_counts(lines: &[Line]) -> (u32, u32) {
+	let count = |text: &str, label: &str| -> u32 {
+		text.split(';')
+			.find_map(|part| {
+				part.trim()
+					.trim_start_matches("test result: ok.")
+					.trim_start_matches("test result: FAILED.")
+					.trim()
+					.strip_suffix(label)?
+					.trim()
+					.parse()
+					.ok()
+			})
+			.unwrap_or(0)
+	};
+
+	lines
+		.iter()
+		.filter(|line| line.text.starts_with("test result:"))
+		.fold((0, 0), |(passed, failed), line| {
+			(
+				passed + count(&line.text, "passed"),
+				failed + count(&line.text, "failed"),
+			)
+		})
+}
+
+/// Binary built by `cargo build --message-format=json`, from the compiler-artifact messages
+pub fn executable(lines: &[Line]) -> Option<PathBuf> {
+	lines
+		.iter()
+		.rev()
+		.filter_map(|line| serde_json::from_str::<serde_json::Value>(&line.text).ok())
+		.filter(|message| message["reason"] == "compiler-artifact")
+		.filter_map(|message| message["executable"].as_str().map(PathBuf::from))
+		.next()
+}
+
+/// Outcome of one step of a watch run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+	pub name: String,
+	pub passed: bool,
+	pub detail: String,
+	pub time: Duration,
+}
+
+/// Renders the steps and answers of a run as a compact summary
+pub fn render_summary(steps: &[Step], answers: &[PartAnswer]) -> String {
+	let mut rows = steps
+		.iter()
+		.map(|step| {
+			[
+				step.name.clone(),
+				if step.passed { "ok" } else { "FAILED" }.to_string(),
+				format!("{:.2?}", step.time),
+				step.detail.clone(),
+			]
+		})
+		.collect::<Vec<_>>();
+	rows.extend(answers.iter().map(|answer| {
+		[
+			format!("part {}", answer.part),
+			answer.answer.clone(),
+			format!("{:.2?}", answer.time),
+			String::new(),
+		]
+	}));
+
+	let widths = (0..3)
+		.map(|column| rows.iter().map(|row| row[column].len()).max().unwrap_or(0))
+		.collect::<Vec<_>>();
+	let mut summary = String::new();
+	for row in rows {
+		let line = format!(
+			"  {:w0$}  {:w1$}  {:>w2$}  {}",
+			row[0],
+			row[1],
+			row[2],
+			row[3],
+			w0 = widths[0],
+			w1 = widths[1],
+			w2 = widths[2]
+		);
+		summary.push_str(line.trim_end());
+		summary.push('\n');
+	}
+	summary
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		executable, parse_duration, part_answers, render_summary, run_cancellable, test_counts,
+		Line, PartAnswer, Snapshot, Step, Watcher,
+	};
+	use std::fs;
+	use std::path::PathBuf;
+	use std::process::Command;
+	use std::time::{Duration, Instant};
+
+	fn line(text: &str) -> Line {
+		Line {
+			text: text.to_string(),
+		}
+	}
+
+	#[test]
+	fn detects_changes() {
+		let dir = tempfile::tempdir().unwrap();
+		let src = dir.path().join("src");
+		fs::create_dir(&src).unwrap();
+		fs::write(src.join("lib.rs"), "").unwrap();
+		let input = dir.path().join("input.txt");
+
+		let mut watcher = Watcher::new(vec![src.clone(), input.clone()]);
+		assert!(!watcher.changed());
+
+		fs::write(src.join("lib.rs"), "fn a() {}").unwrap();
+		assert!(watcher.changed());
+		assert!(!watcher.changed());
+
+		fs::write(&input, "1\n").unwrap();
+		assert!(watcher.changed());
+		fs::create_dir(src.join("bin")).unwrap();
+		fs::write(src.join("bin").join("a.rs"), "").unwrap();
+		assert!(watcher.changed());
+		fs::remove_file(&input).unwrap();
+		assert!(watcher.changed());
+
+		assert_eq!(
+			Snapshot::take(&[dir.path().join("missing")]),
+			Snapshot::default()
+		);
+	}
+
+	#[test]
+	fn durations() {
+		assert_eq!(parse_duration("1.5ms"), Some(Duration::from_micros(1500)));
+		assert_eq!(parse_duration("2.000000001s"), Some(Duration::new(2, 1)));
+		assert_eq!(parse_duration("12ns"), Some(Duration::from_nanos(12)));
+		assert_eq!(
+			parse_duration(&format!("{:?}", Duration::from_nanos(123_456_789))),
+			Some(Duration::from_nanos(123_456_789))
+		);
+		assert_eq!(parse_duration("5 apples"), None);
+		assert_eq!(parse_duration("ms"), None);
+	}
+
+	#[test]
+	fn answers_and_test_counts() {
+		let lines = [
+			line("Compiling"),
+			line("Part 1: 1006875 (1.5ms)"),
+			line("Part 2: (1, 2) (330µs)"),
+			line("Part 3: no time"),
+		];
+		assert_eq!(
+			part_answers(&lines),
+			[
+				PartAnswer {
+					part: 1,
+					answer: "1006875".to_string(),
+					time: Duration::from_micros(1500)
+				},
+				PartAnswer {
+					part: 2,
+					answer: "(1, 2)".to_string(),
+					time: Duration::from_micros(330)
+				}
+			]
+		);
+
+		let lines = [
+			line("test result: ok. 4 passed; 0 failed; 0 ignored; 0 measured"),
+			line("test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 measured"),
+			line("test tests::a ... ok"),
+		];
+		assert_eq!(test_counts(&lines), (5, 2));
+
+		let lines = [
+			line(r#"{"reason":"compiler-artifact","target":{"kind":["lib"]},"executable":null}"#),
+			line("error: could not compile"),
+			line(
+				r#"{"reason":"compiler-artifact","target":{"kind":["bin"]},"executable":"/ws/target/release/day_15"}"#,
+			),
+			line(r#"{"reason":"build-finished","success":true}"#),
+		];
+		assert_eq!(
+			executable(&lines),
+			Some(PathBuf::from("/ws/target/release/day_15"))
+		);
+		assert_eq!(executable(&lines[..2]), None);
+	}
+
+	#[test]
+	fn summary() {
+		let steps = [
+			Step {
+				name: "build".to_string(),
+				passed: true,
+				detail: String::new(),
+				time: Duration::from_millis(1500),
+			},
+			Step {
+				name: "tests".to_string(),
+				passed: false,
+				detail: "3 passed, 1 failed".to_string(),
+				time: Duration::from_millis(250),
+			},
+		];
+		let answers = [PartAnswer {
+			part: 1,
+			answer: "42".to_string(),
+			time: Duration::from_micros(1500),
+		}];
+		assert_eq!(
+			render_summary(&steps, &answers),
+			"  build   ok         1.50s
+  tests   FAILED  250.00ms  3 passed, 1 failed
+  part 1  42        1.50ms
+"
+		);
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn commands_finish_or_get_cancelled() {
+		let run = run_cancellable(
+			Command::new("sh").args(["-c", "echo 'Part 1: 3'; echo oops >&2; exit 1"]),
+			|| false,
+		)
+		.unwrap()
+		.unwrap();
+		assert!(!run.success);
+		let mut texts = run
+			.lines
+			.into_iter()
+			.map(|line| line.text)
+			.collect::<Vec<_>>();
+		texts.sort();
+		assert_eq!(texts, ["Part 1: 3", "oops"]);
+
+		let start = Instant::now();
+		let mut polls = 0;
+		let run = run_cancellable(
+			Command::new("sh").args(["-c", "sleep 30; sleep 30"]),
+			|| {
+				polls += 1;
+				polls > 2
+			},
+		)
+		.unwrap();
+		assert!(run.is_none());
+		assert!(start.elapsed() < Duration::from_secs(5));
+	}
+}
@@ -0,0 +1,459 @@
+use std::cmp::Ordering;
+use std::fs;
+use std::io;
+use std::path::Path;
+use thiserror::Error;
+use toml_edit::{Array, DocumentMut, Item, Value};
+
+#[derive(Debug, Error)]
+pub enum CargoTomlParserError {
+	#[error("failed to parse manifest")]
+	Parse(#[from] toml_edit::TomlError),
+	#[error("failed to get \"workspace\" section")]
+	FailedToGetWorkspace,
+	#[error("failed to get \"members\" array")]
+	FailedToGetMembers,
+	#[error("failed to get \"dependencies\" section")]
+	FailedToGetDependencies,
+	#[error("\"{0}\" is already a workspace member")]
+	DuplicateMember(String),
+}
+
+/// Day crate of a workspace that may hold several events.
+///
+/// Days of the default year can live directly in the workspace root (`day_N`), other years are
+/// kept in their own directory (`y2021/day_N`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayCrate {
+	pub year: u32,
+	pub day: u32,
+	/// Whether the crate lives in a `yYYYY` directory
+	pub nested: bool,
+}
+
+impl DayCrate {
+	/// Parses a workspace member path, flat members belong to `default_year`
+	pub fn from_member(member: &str, default_year: u32) -> Option<DayCrate> {
+		let (year, nested, name) = match member.split_once('/') {
+			Some((year_dir, name)) => (year_dir.strip_prefix('y')?.parse().ok()?, true, name),
+			None => (default_year, false, member),
+		};
+		let day = name.strip_prefix("day_")?.parse().ok()?;
+
+		Some(DayCrate { year, day, nested })
+	}
+
+	/// Path of the crate relative to the workspace root, as listed in `members`
+	pub fn member(&self) -> String {
+		if self.nested {
+			format!("y{}/day_{}", self.year, self.day)
+		} else {
+			format!("day_{}", self.day)
+		}
+	}
+
+	/// Package name, nested crates are prefixed with their year to keep names unique
+	pub fn name(&self) -> String {
+		if self.nested {
+			format!("y{}_day_{}", self.year, self.day)
+		} else {
+			format!("day_{}", self.day)
+		}
+	}
+
+	/// Relative path from the crate directory back to the workspace root
+	pub fn workspace_root(&self) -> &'static str {
+		if self.nested {
+			"../.."
+		} else {
+			".."
+		}
+	}
+}
+
+/// Cargo.toml edited in place, formatting and comments of untouched parts are kept as is
+pub struct Manifest {
+	doc: DocumentMut,
+}
+
+impl Manifest {
+	pub fn parse(manifest: &str) -> Result<Manifest, CargoTomlParserError> {
+		Ok(Manifest {
+			doc: manifest.parse()?,
+		})
+	}
+
+	fn members_array(&self) -> Result<&Array, CargoTomlParserError> {
+		self.doc
+			.get("workspace")
+			.ok_or(CargoTomlParserError::FailedToGetWorkspace)?
+			.get("members")
+			.and_then(Item::as_array)
+			.ok_or(CargoTomlParserError::FailedToGetMembers)
+	}
+
+	fn members_array_mut(&mut self) -> Result<&mut Array, CargoTomlParserError> {
+		self.doc
+			.get_mut("workspace")
+			.ok_or(CargoTomlParserError::FailedToGetWorkspace)?
+			.get_mut("members")
+			.and_then(Item::as_array_mut)
+			.ok_or(CargoTomlParserError::FailedToGetMembers)
+	}
+
+	pub fn members(&self) -> Result<Vec<String>, CargoTomlParserError> {
+		Ok(self
+			.members_array()?
+			.iter()
+			.filter_map(Value::as_str)
+			.map(ToOwned::to_owned)
+			.collect())
+	}
+
+	/// Day crates of the given year listed in the workspace members
+	pub fn day_crates(
+		&self,
+		year: u32,
+		default_year: u32,
+	) -> Result<Vec<DayCrate>, CargoTomlParserError> {
+		Ok(self
+			.members()?
+			.iter()
+			.filter_map(|member| DayCrate::from_member(member, default_year))
+			.filter(|day_crate| day_crate.year == year)
+			.collect())
+	}
+
+	/// Whether the days of a year live in a `yYYYY` directory.
+	///
+	/// The default year stays flat unless its days were moved to a `yYYYY` directory, other
+	/// years are always nested.
+	pub fn is_nested(&self, year: u32, default_year: u32) -> Result<bool, CargoTomlParserError> {
+		Ok(year != default_year
+			|| self
+				.day_crates(year, default_year)?
+				.iter()
+				.any(|day_crate| day_crate.nested))
+	}
+
+	/// Location of a day crate, existing members are reused as they are
+	pub fn day_crate(
+		&self,
+		year: u32,
+		day: u32,
+		default_year: u32,
+	) -> Result<DayCrate, CargoTomlParserError> {
+		let existing = self
+			.day_crates(year, default_year)?
+			.into_iter()
+			.find(|day_crate| day_crate.day == day);
+
+		match existing {
+			Some(day_crate) => Ok(day_crate),
+			None => Ok(DayCrate {
+				year,
+				day,
+				nested: self.is_nested(year, default_year)?,
+			}),
+		}
+	}
+
+	/// Inserts a workspace member keeping the list in natural order (`day_9` before `day_10`).
+	///
+	/// The new entry copies the indentation and quote style of its neighbour.
+	pub fn insert_member(&mut self, member: &str) -> Result<(), CargoTomlParserError> {
+		let members = self.members_array_mut()?;
+
+		if members
+			.iter()
+			.filter_map(Value::as_str)
+			.any(|m| m == member)
+		{
+			return Err(CargoTomlParserError::DuplicateMember(member.to_string()));
+		}
+
+		let idx = members
+			.iter()
+			.position(|m| {
+				m.as_str()
+					.map(|m| natural_cmp(m, member) == Ordering::Greater)
+					.unwrap_or(false)
+			})
+			.unwrap_or_else(|| members.len());
+
+		let neighbour = members.get(idx).or_else(|| members.iter().last());
+		let neighbour = match neighbour {
+			Some(v) => v,
+			None => {
+				members.push(member);
+				return Ok(());
+			}
+		};
+
+		let literal = match neighbour {
+			Value::String(s) => s
+				.as_repr()
+				.and_then(|repr| repr.as_raw().as_str())
+				.map(|raw| raw.starts_with('\''))
+				.unwrap_or(false),
+			_ => false,
+		};
+		let mut value = if literal && !member.contains('\'') {
+			// SAFETY: a literal string without quotes inside is always valid
+			format!("'{}'", member).parse::<Value>().unwrap()
+		} else {
+			Value::from(member)
+		};
+		*value.decor_mut() = neighbour.decor().clone();
+
+		members.insert_formatted(idx, value);
+
+		Ok(())
+	}
+
+	/// Inserts or replaces a `[dependencies]` entry
+	pub fn set_dependency(&mut self, name: &str, value: Item) -> Result<(), CargoTomlParserError> {
+		let dependencies = self
+			.doc
+			.get_mut("dependencies")
+			.and_then(Item::as_table_like_mut)
+			.ok_or(CargoTomlParserError::FailedToGetDependencies)?;
+		dependencies.insert(name, value);
+
+		Ok(())
+	}
+}
+
+impl std::fmt::Display for Manifest {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.doc)
+	}
+}
+
+/// Returns the lowest `N` of the `day_N` directories in `year_dir` without an `input.txt`,
+/// a missing `year_dir` has no days
+pub fn first_day_without_input(year_dir: &Path) -> io::Result<Option<u32>> {
+	let entries = match fs::read_dir(year_dir) {
+		Ok(v) => v,
+		Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+		Err(e) => return Err(e),
+	};
+
+	let mut first = None;
+	for entry in entries {
+		let path = entry?.path();
+		let day = path
+			.file_name()
+			.and_then(|name| name.to_str())
+			.and_then(|name| name.strip_prefix("day_"))
+			.and_then(|day| day.parse::<u32>().ok());
+
+		if let Some(day) = day {
+			if path.is_dir() && !path.join("input.txt").exists() {
+				first = Some(first.map_or(day, |first: u32| first.min(day)));
+			}
+		}
+	}
+
+	Ok(first)
+}
+
+/// Compares strings chunk by chunk, runs of digits are compared by their numeric value
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+	let (mut a, mut b) = (a, b);
+	loop {
+		match (a.is_empty(), b.is_empty()) {
+			(true, true) => return Ordering::Equal,
+			(true, false) => return Ordering::Less,
+			(false, true) => return Ordering::Greater,
+			_ => (),
+		}
+
+		let (a_chunk, a_rest) = split_chunk(a);
+		let (b_chunk, b_rest) = split_chunk(b);
+
+		let ordering = match (a_chunk.parse::<u64>(), b_chunk.parse::<u64>()) {
+			(Ok(a_num), Ok(b_num)) => a_num.cmp(&b_num),
+			_ => a_chunk.cmp(b_chunk),
+		};
+		if ordering != Ordering::Equal {
+			return ordering;
+		}
+
+		a = a_rest;
+		b = b_rest;
+	}
+}
+
+fn split_chunk(s: &str) -> (&str, &str) {
+	let digits = s.starts_with(|c: char| c.is_ascii_digit());
+	let end = s
+		.find(|c: char| c.is_ascii_digit() != digits)
+		.unwrap_or(s.len());
+	s.split_at(end)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{first_day_without_input, natural_cmp, CargoTomlParserError, DayCrate, Manifest};
+	use std::cmp::Ordering;
+	use std::fs;
+
+	const WORKSPACE: &str = "[workspace]
+members = [
+    'aqa_aoc',
+    'day_1',
+    'day_2',
+    'day_4',
+    'day_10',
+]
+";
+
+	#[test]
+	fn member_is_inserted_in_order() {
+		let mut manifest = Manifest::parse(WORKSPACE).unwrap();
+		manifest.insert_member("day_3").unwrap();
+		assert_eq!(
+			manifest.to_string(),
+			WORKSPACE.replace("    'day_4',", "    'day_3',\n    'day_4',")
+		);
+
+		let mut manifest = Manifest::parse(WORKSPACE).unwrap();
+		manifest.insert_member("day_11").unwrap();
+		assert_eq!(
+			manifest.to_string(),
+			WORKSPACE.replace("    'day_10',", "    'day_10',\n    'day_11',")
+		);
+	}
+
+	#[test]
+	fn comments_and_quotes_are_kept() {
+		let workspace = "# Advent of Code 2020
+[workspace]
+members = [\"aqa_aoc\", \"day_1\"] # keep it short
+
+[profile.release]
+debug = true
+";
+		let mut manifest = Manifest::parse(workspace).unwrap();
+		manifest.insert_member("day_2").unwrap();
+		assert_eq!(
+			manifest.to_string(),
+			workspace.replace("\"day_1\"]", "\"day_1\", \"day_2\"]")
+		);
+	}
+
+	#[test]
+	fn duplicate_member_is_rejected() {
+		let mut manifest = Manifest::parse(WORKSPACE).unwrap();
+		assert!(matches!(
+			manifest.insert_member("day_4"),
+			Err(CargoTomlParserError::DuplicateMember(member)) if member == "day_4"
+		));
+		assert_eq!(manifest.to_string(), WORKSPACE);
+	}
+
+	#[test]
+	fn dependencies_keep_formatting() {
+		let crate_manifest = "[package]
+name = \"day_19\"
+version = \"0.1.0\"
+
+[dependencies]
+";
+		let mut manifest = Manifest::parse(crate_manifest).unwrap();
+		manifest
+			.set_dependency("anyhow", toml_edit::value("1.0"))
+			.unwrap();
+		assert_eq!(
+			manifest.to_string(),
+			format!("{}anyhow = \"1.0\"\n", crate_manifest)
+		);
+	}
+
+	#[test]
+	fn day_crates_of_several_years() {
+		let mut manifest = Manifest::parse(WORKSPACE).unwrap();
+		manifest.insert_member("y2021/day_1").unwrap();
+		manifest.insert_member("y2021/day_2").unwrap();
+		assert!(manifest
+			.to_string()
+			.ends_with("    'day_10',\n    'y2021/day_1',\n    'y2021/day_2',\n]\n"));
+
+		let days = |year| {
+			manifest
+				.day_crates(year, 2020)
+				.unwrap()
+				.iter()
+				.map(|day_crate| day_crate.member())
+				.collect::<Vec<_>>()
+		};
+		assert_eq!(days(2020), ["day_1", "day_2", "day_4", "day_10"]);
+		assert_eq!(days(2021), ["y2021/day_1", "y2021/day_2"]);
+		assert!(days(2019).is_empty());
+
+		let new_day = manifest.day_crate(2020, 3, 2020).unwrap();
+		assert_eq!(
+			(new_day.member(), new_day.name()),
+			("day_3".into(), "day_3".into())
+		);
+		let new_day = manifest.day_crate(2021, 3, 2020).unwrap();
+		assert_eq!(
+			(new_day.member(), new_day.name()),
+			("y2021/day_3".into(), "y2021_day_3".into())
+		);
+		assert_eq!(new_day.workspace_root(), "../..");
+	}
+
+	#[test]
+	fn nested_default_year() {
+		let manifest = Manifest::parse("[workspace]\nmembers = ['y2020/day_1']\n").unwrap();
+		assert!(manifest.day_crate(2020, 2, 2020).unwrap().nested);
+
+		assert_eq!(DayCrate::from_member("aqa_aoc", 2020), None);
+		assert_eq!(DayCrate::from_member("x2020/day_1", 2020), None);
+		assert_eq!(
+			DayCrate::from_member("y2019/day_25", 2020),
+			Some(DayCrate {
+				year: 2019,
+				day: 25,
+				nested: true
+			})
+		);
+	}
+
+	#[test]
+	fn lowest_day_without_input() {
+		let dir = tempfile::tempdir().unwrap();
+		assert_eq!(
+			first_day_without_input(&dir.path().join("y2021")).unwrap(),
+			None
+		);
+
+		// Created in an order that doesn't match the day numbers
+		for day in [17, 3, 10, 2] {
+			fs::create_dir(dir.path().join(format!("day_{}", day))).unwrap();
+		}
+		fs::write(dir.path().join("day_2").join("input.txt"), "1\n").unwrap();
+		fs::create_dir(dir.path().join("day_x")).unwrap();
+		fs::write(dir.path().join("day_1"), "not a directory").unwrap();
+		assert_eq!(first_day_without_input(dir.path()).unwrap(), Some(3));
+
+		for day in [3, 10, 17] {
+			fs::write(
+				dir.path().join(format!("day_{}", day)).join("input.txt"),
+				"1\n",
+			)
+			.unwrap();
+		}
+		assert_eq!(first_day_without_input(dir.path()).unwrap(), None);
+	}
+
+	#[test]
+	fn natural_order() {
+		assert_eq!(natural_cmp("day_9", "day_10"), Ordering::Less);
+		assert_eq!(natural_cmp("day_10", "day_10"), Ordering::Equal);
+		assert_eq!(natural_cmp("day_2", "aqa_aoc"), Ordering::Greater);
+		assert_eq!(natural_cmp("day_1", "day_1_b"), Ordering::Less);
+	}
+}
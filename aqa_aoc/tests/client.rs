@@ -0,0 +1,238 @@
+mod common;
+
+use aqa_aoc::client::{AocClient, AocError};
+use aqa_aoc::submit::SubmissionResult;
+use aqa_aoc::unlock::Backoff;
+use common::{FakeClock, MockServer};
+use std::net::TcpListener;
+use std::time::{Duration, UNIX_EPOCH};
+
+#[test]
+fn input_is_fetched_with_session_cookie() {
+	let server = MockServer::start();
+	server.route("/2020/day/1/input", 200, "1721\n979\n");
+
+	let client = AocClient::new(server.addr(), "abc123").unwrap();
+	assert_eq!(client.get_input(2020, 1).unwrap(), "1721\n979\n");
+
+	let requests = server.requests();
+	assert_eq!(requests.len(), 1);
+	assert_eq!(requests[0].method, "GET");
+	assert_eq!(requests[0].path, "/2020/day/1/input");
+	assert_eq!(
+		requests[0].headers.get("cookie").map(String::as_str),
+		Some("session=abc123")
+	);
+}
+
+#[test]
+fn synthetic_error() {
+	// Bind and drop a listener to get a port nobody listens on
+	let addr = {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		format!("http://{}", listener.local_addr().unwrap())
+	};
+
+	let client = AocClient::new(&addr, "abc123").unwrap();
+	let err = client.get_input(2020, 1).unwrap_err();
+	assert!(matches!(err, AocError::Synthetic(_)), "{:?}", err);
+}
+
+#[test]
+fn please_log_in() {
+	let server = MockServer::start();
+	server.route(
+		"/2020/day/1/input",
+		400,
+		"Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+	);
+
+	let client = AocClient::new(server.addr(), "expired").unwrap();
+	match client.get_input(2020, 1) {
+		Err(AocError::NotLoggedIn(msg)) => assert_eq!(
+			msg,
+			"Puzzle inputs differ by user.  Please log in to get your puzzle input."
+		),
+		other => panic!("unexpected result: {:?}", other),
+	}
+}
+
+#[test]
+fn locked_day() {
+	let server = MockServer::start();
+
+	let client = AocClient::new(server.addr(), "abc123").unwrap();
+	let err = client.get_input(2020, 25).unwrap_err();
+	match err {
+		AocError::NotFound(url) => assert!(url.ends_with("/2020/day/25/input"), "{}", url),
+		other => panic!("unexpected error: {:?}", other),
+	}
+
+	let err = client.get_puzzle_page(2020, 25).unwrap_err();
+	assert!(matches!(err, AocError::NotFound(_)), "{:?}", err);
+}
+
+#[test]
+fn locked_day_is_retried() {
+	let server = MockServer::start();
+
+	let clock = FakeClock::at(UNIX_EPOCH);
+	let backoff = Backoff {
+		attempts: 3,
+		..Backoff::at_least(Duration::from_secs(5))
+	};
+	let client = AocClient::new(server.addr(), "abc123")
+		.unwrap()
+		.with_unlock_retry(backoff, Box::new(clock.clone()));
+	let err = client.get_input(2020, 25).unwrap_err();
+	assert!(matches!(err, AocError::NotFound(_)), "{:?}", err);
+
+	assert_eq!(server.requests().len(), 3);
+	assert_eq!(clock.sleeps(), [5, 8].map(Duration::from_secs));
+}
+
+#[test]
+fn expired_cookie() {
+	let server = MockServer::start();
+	server.route("/2020/day/1/input", 500, "Internal Server Error");
+	server.route(
+		"/2020",
+		200,
+		"<header><nav><a href=\"/2020/auth/login\">[Log In]</a></nav></header>",
+	);
+
+	let client = AocClient::new(server.addr(), "expired").unwrap();
+	assert_eq!(client.whoami(2020).unwrap(), None);
+	let err = client.get_input(2020, 1).unwrap_err();
+	assert!(matches!(err, AocError::NotLoggedIn(_)), "{:?}", err);
+}
+
+#[test]
+fn cookie_is_checked_once() {
+	let server = MockServer::start();
+	server.route("/2020/day/1/input", 500, "Internal Server Error");
+	server.route("/2020/day/2/input", 503, "Service Unavailable");
+	server.route(
+		"/2020",
+		200,
+		"<header><nav><a href=\"/2020/auth/login\">[Log In]</a></nav></header>",
+	);
+
+	let client = AocClient::new(server.addr(), "expired").unwrap();
+	// An outage isn't blamed on the cookie
+	let err = client.get_input(2020, 2).unwrap_err();
+	assert!(
+		matches!(err, AocError::Status { status: 503, .. }),
+		"{:?}",
+		err
+	);
+	for _ in 0..2 {
+		let err = client.get_input(2020, 1).unwrap_err();
+		assert!(matches!(err, AocError::NotLoggedIn(_)), "{:?}", err);
+	}
+
+	let paths = server
+		.requests()
+		.into_iter()
+		.map(|request| request.path)
+		.collect::<Vec<_>>();
+	assert_eq!(
+		paths,
+		[
+			"/2020/day/2/input",
+			"/2020/day/1/input",
+			"/2020",
+			"/2020/day/1/input"
+		]
+	);
+}
+
+#[test]
+fn whoami() {
+	let server = MockServer::start();
+	server.route(
+		"/2020",
+		200,
+		"<header><div class=\"user\">aQaTL <span class=\"star-count\">36*</span></div></header>",
+	);
+
+	let client = AocClient::new(server.addr(), "abc123").unwrap();
+	assert_eq!(client.whoami(2020).unwrap().as_deref(), Some("aQaTL"));
+}
+
+#[test]
+fn server_error() {
+	let server = MockServer::start();
+	server.route("/2020/day/1/input", 500, "Internal Server Error");
+	server.route("/2020/day/1/answer", 500, "Internal Server Error");
+
+	let client = AocClient::new(server.addr(), "abc123").unwrap();
+	match client.get_input(2020, 1) {
+		Err(AocError::Status {
+			action,
+			status,
+			status_line,
+		}) => {
+			assert_eq!(action, "fetch input");
+			assert_eq!(status, 500);
+			assert_eq!(status_line, "HTTP/1.1 500 Internal Server Error");
+		}
+		other => panic!("unexpected result: {:?}", other),
+	}
+
+	let err = client.submit_answer(2020, 1, 1, "42").unwrap_err();
+	assert!(matches!(err, AocError::Status { .. }), "{:?}", err);
+}
+
+#[test]
+fn answer_is_submitted_as_form() {
+	let server = MockServer::start();
+	server.route(
+		"/2020/day/1/answer",
+		200,
+		"<main><article><p>That's the right answer!</p></article></main>",
+	);
+
+	let client = AocClient::new(server.addr(), "abc123").unwrap();
+	assert_eq!(
+		client.submit_answer(2020, 1, 2, "42").unwrap(),
+		SubmissionResult::Correct
+	);
+
+	let requests = server.requests();
+	assert_eq!(requests[0].method, "POST");
+	assert_eq!(requests[0].body, "level=2&answer=42");
+}
+
+#[test]
+fn private_leaderboard() {
+	let server = MockServer::start();
+	let fixture = include_str!("fixtures/leaderboard.json");
+	server.route("/2020/leaderboard/private/view/123456.json", 200, fixture);
+	server.route(
+		"/2020/leaderboard/private/view/1.json",
+		200,
+		"<html><body>[Log In]</body></html>",
+	);
+
+	let client = AocClient::new(server.addr(), "abc123").unwrap();
+	assert_eq!(
+		client.get_private_leaderboard(2020, 123456).unwrap(),
+		fixture
+	);
+
+	let err = client.get_private_leaderboard(2020, 1).unwrap_err();
+	assert!(
+		matches!(err, AocError::LeaderboardNotAccessible(1)),
+		"{:?}",
+		err
+	);
+}
+
+#[test]
+fn invalid_base_url() {
+	assert!(matches!(
+		AocClient::new("not a url", "abc123"),
+		Err(AocError::InvalidBaseUrl(_))
+	));
+}
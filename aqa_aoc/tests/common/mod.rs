@@ -0,0 +1,179 @@
+//! In-process stand-in for the AoC site and a clock for the unlock retries
+
+// Every test crate only uses part of it
+#![allow(dead_code)]
+
+use aqa_aoc::unlock::Clock;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, Clone)]
+pub struct Request {
+	pub method: String,
+	pub path: String,
+	pub headers: HashMap<String, String>,
+	pub body: String,
+}
+
+#[derive(Clone)]
+pub struct MockResponse {
+	pub status: u16,
+	pub body: String,
+}
+
+pub struct MockServer {
+	addr: String,
+	routes: Arc<Mutex<HashMap<String, MockResponse>>>,
+	requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockServer {
+	/// Starts the server on a random port, requests to unknown paths get a 404
+	pub fn start() -> MockServer {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let addr = format!("http://{}", listener.local_addr().unwrap());
+
+		let routes = Arc::new(Mutex::new(HashMap::new()));
+		let requests = Arc::new(Mutex::new(Vec::new()));
+
+		let (server_routes, server_requests) = (Arc::clone(&routes), Arc::clone(&requests));
+		thread::spawn(move || {
+			for stream in listener.incoming().filter_map(Result::ok) {
+				handle_connection(stream, &server_routes, &server_requests);
+			}
+		});
+
+		MockServer {
+			addr,
+			routes,
+			requests,
+		}
+	}
+
+	pub fn addr(&self) -> &str {
+		&self.addr
+	}
+
+	pub fn route(&self, path: &str, status: u16, body: &str) {
+		self.routes.lock().unwrap().insert(
+			path.to_string(),
+			MockResponse {
+				status,
+				body: body.to_string(),
+			},
+		);
+	}
+
+	pub fn requests(&self) -> Vec<Request> {
+		self.requests.lock().unwrap().clone()
+	}
+}
+
+fn handle_connection(
+	stream: TcpStream,
+	routes: &Mutex<HashMap<String, MockResponse>>,
+	requests: &Mutex<Vec<Request>>,
+) {
+	let mut reader = BufReader::new(&stream);
+
+	let mut request_line = String::new();
+	if reader.read_line(&mut request_line).is_err() {
+		return;
+	}
+	let mut request_line = request_line.split_whitespace();
+	let method = request_line.next().unwrap_or_default().to_string();
+	let path = request_line.next().unwrap_or_default().to_string();
+
+	let mut headers = HashMap::new();
+	loop {
+		let mut line = String::new();
+		if reader.read_line(&mut line).is_err() || line.trim().is_empty() {
+			break;
+		}
+		if let Some((name, value)) = line.split_once(':') {
+			headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+		}
+	}
+
+	let content_length = headers
+		.get("content-length")
+		.and_then(|len| len.parse::<usize>().ok())
+		.unwrap_or(0);
+	let mut body = vec![0; content_length];
+	let _ = reader.read_exact(&mut body);
+
+	let response = routes
+		.lock()
+		.unwrap()
+		.get(&path)
+		.cloned()
+		.unwrap_or(MockResponse {
+			status: 404,
+			body: "404 Not Found".to_string(),
+		});
+
+	requests.lock().unwrap().push(Request {
+		method,
+		path,
+		headers,
+		body: String::from_utf8_lossy(&body).into_owned(),
+	});
+
+	let mut stream = stream;
+	let _ = write!(
+		stream,
+		"HTTP/1.1 {} {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+		response.status,
+		reason(response.status),
+		response.body.len(),
+		response.body
+	);
+}
+
+fn reason(status: u16) -> &'static str {
+	match status {
+		200 => "OK",
+		400 => "Bad Request",
+		404 => "Not Found",
+		500 => "Internal Server Error",
+		_ => "Unknown",
+	}
+}
+
+/// Clock that moves forward only when slept on, its clones share the same time
+#[derive(Debug, Clone)]
+pub struct FakeClock {
+	now: Rc<Cell<SystemTime>>,
+	sleeps: Rc<RefCell<Vec<Duration>>>,
+}
+
+impl FakeClock {
+	pub fn at(now: SystemTime) -> FakeClock {
+		FakeClock {
+			now: Rc::new(Cell::new(now)),
+			sleeps: Rc::new(RefCell::new(Vec::new())),
+		}
+	}
+
+	/// Every duration slept so far
+	pub fn sleeps(&self) -> Vec<Duration> {
+		self.sleeps.borrow().clone()
+	}
+}
+
+impl Clock for FakeClock {
+	fn now(&self) -> SystemTime {
+		self.now.get()
+	}
+
+	fn sleep(&self, duration: Duration) {
+		self.now.set(self.now.get() + duration);
+		self.sleeps.borrow_mut().push(duration);
+	}
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use aqa_aoc::client::AocError;
+use aqa_aoc::unlock::{format_countdown, retry_not_found, unlock_time, wait_until, Backoff, Clock};
+use common::FakeClock;
+use std::time::{Duration, UNIX_EPOCH};
+
+#[test]
+fn countdown_reaches_unlock() {
+	let unlock = unlock_time(2020, 1);
+	let clock = FakeClock::at(unlock - Duration::from_millis(2500));
+
+	let mut ticks = Vec::new();
+	wait_until(&clock, unlock, |remaining| {
+		ticks.push(format_countdown(remaining))
+	});
+
+	assert_eq!(clock.now(), unlock);
+	assert_eq!(ticks, ["00:00:03", "00:00:02", "00:00:01"]);
+
+	let mut ticks = 0;
+	wait_until(&clock, unlock - Duration::from_secs(1), |_| ticks += 1);
+	assert_eq!(ticks, 0);
+}
+
+#[test]
+fn retries_until_found() {
+	let clock = FakeClock::at(UNIX_EPOCH);
+	let backoff = Backoff {
+		initial: Duration::from_secs(1),
+		max: Duration::from_secs(3),
+		attempts: 10,
+	};
+
+	let mut attempts = 0;
+	let result = retry_not_found(&clock, &backoff, || {
+		attempts += 1;
+		if attempts < 5 {
+			Err(AocError::NotFound("input".to_string()))
+		} else {
+			Ok(attempts)
+		}
+	});
+
+	assert_eq!(result.unwrap(), 5);
+	assert_eq!(clock.sleeps(), [1, 2, 3, 3].map(Duration::from_secs));
+}
+
+#[test]
+fn gives_up_and_keeps_other_errors() {
+	let clock = FakeClock::at(UNIX_EPOCH);
+	let backoff = Backoff {
+		attempts: 3,
+		..Backoff::default()
+	};
+
+	let mut attempts = 0;
+	let result: Result<(), _> = retry_not_found(&clock, &backoff, || {
+		attempts += 1;
+		Err(AocError::NotFound("input".to_string()))
+	});
+	assert!(matches!(result, Err(AocError::NotFound(_))));
+	assert_eq!(attempts, 3);
+
+	let result: Result<(), _> = retry_not_found(&clock, &backoff, || {
+		Err(AocError::NotLoggedIn(String::new()))
+	});
+	assert!(matches!(result, Err(AocError::NotLoggedIn(_))));
+	assert_eq!(clock.sleeps().len(), 2);
+}
@@ -0,0 +1,57 @@
+use anyhow::{anyhow, Result};
+use aoc_solution::Solution;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+	type Input<'a> = Vec<u64>;
+	type Part1 = u64;
+	type Part2 = u64;
+
+	fn parse(input: &str) -> Result<Vec<u64>> {
+		input
+			.lines()
+			.map(|x| x.parse::<u64>().map_err(Into::into))
+			.collect()
+	}
+
+	fn part_1(input: &Vec<u64>) -> Result<u64> {
+		part_1(input)
+	}
+
+	fn part_2(input: &Vec<u64>) -> Result<u64> {
+		part_2(input)
+	}
+}
+
+fn part_1(input: &[u64]) -> Result<u64> {
+	input
+		.iter()
+		.enumerate()
+		.map(|(a_idx, a)| ((a_idx, a), input.iter()))
+		.find_map(|((a_idx, &a), b)| b.skip(a_idx).find(|&&b| b + a == 2020).map(|b| b * a))
+		.ok_or_else(|| anyhow!("no two entries sum to 2020"))
+}
+
+fn part_2(input: &[u64]) -> Result<u64> {
+	input
+		.iter()
+		.enumerate()
+		.map(|(a_idx, a)| {
+			(
+				(a_idx, a),
+				input
+					.iter()
+					.enumerate()
+					.map(|(b_idx, b)| ((b_idx, b), input.iter())),
+			)
+		})
+		.find_map(|((a_idx, &a), b)| {
+			b.skip(a_idx).find_map(|((b_idx, &b), c)| {
+				c.skip(b_idx)
+					.find(|&&c| c + b + a == 2020)
+					.map(|c| c * b * a)
+			})
+		})
+		.ok_or_else(|| anyhow!("no three entries sum to 2020"))
+}
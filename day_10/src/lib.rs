@@ -0,0 +1,96 @@
+use anyhow::Result;
+use aoc_solution::Solution;
+use itertools::Itertools;
+use std::collections::HashMap;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+	type Input<'a> = Vec<u64>;
+	type Part1 = u64;
+	type Part2 = u64;
+
+	fn parse(input: &str) -> Result<Vec<u64>> {
+		input
+			.lines()
+			.map(str::parse)
+			.try_collect()
+			.map_err(Into::into)
+	}
+
+	fn part_1(input: &Vec<u64>) -> Result<u64> {
+		Ok(part_1(input.clone()))
+	}
+
+	fn part_2(input: &Vec<u64>) -> Result<u64> {
+		Ok(part_2(input.clone()))
+	}
+}
+
+fn part_1(mut input: Vec<u64>) -> u64 {
+	input.sort();
+
+	let (ones, threes): (Vec<u64>, Vec<u64>) = (0..=0)
+		.chain(input)
+		.tuple_windows()
+		.map(|(a, b)| b - a)
+		.partition(|diff| *diff == 1);
+
+	ones.len() as u64 * (threes.len() as u64 + 1)
+}
+
+fn part_2(mut input: Vec<u64>) -> u64 {
+	input.sort();
+	input.insert(0, 0);
+	input.push(*input.last().unwrap() + 3);
+
+	calc(&mut HashMap::new(), &input, 0)
+}
+
+fn calc(mem: &mut HashMap<usize, u64>, input: &[u64], idx: usize) -> u64 {
+	if idx <= input.len() - 4 {
+		let a = if input[idx + 1] - input[idx] <= 3 {
+			get_or_calc(mem, input, idx + 1)
+		} else {
+			0
+		};
+		let b = if input[idx + 2] - input[idx] <= 3 {
+			get_or_calc(mem, input, idx + 2)
+		} else {
+			0
+		};
+		let c = if input[idx + 3] - input[idx] <= 3 {
+			get_or_calc(mem, input, idx + 3)
+		} else {
+			0
+		};
+		a + b + c
+	} else if idx == input.len() - 3 {
+		let a = if input[idx + 1] - input[idx] <= 3 {
+			get_or_calc(mem, input, idx + 1)
+		} else {
+			0
+		};
+		let b = if input[idx + 2] - input[idx] <= 3 {
+			get_or_calc(mem, input, idx + 2)
+		} else {
+			0
+		};
+		a + b
+	} else if idx == input.len() - 2 {
+		1
+	} else {
+		0
+	}
+}
+
+fn get_or_calc(mem: &mut HashMap<usize, u64>, input: &[u64], idx: usize) -> u64 {
+	match mem.get(&idx).copied() {
+		Some(v) => v,
+		None => {
+			let x = calc(mem, input, idx);
+			mem.insert(idx, x);
+			x
+		}
+	}
+}
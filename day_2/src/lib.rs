@@ -0,0 +1,76 @@
+use anyhow::Result;
+use aoc_solution::Solution;
+
+pub struct Day2;
+
+pub struct Policy<'a> {
+	low: usize,
+	high: usize,
+	letter: u8,
+	password: &'a str,
+}
+
+impl Solution for Day2 {
+	type Input<'a> = Vec<Policy<'a>>;
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Result<Vec<Policy<'_>>> {
+		let re = regex::Regex::new(r"(\d+)-(\d+)\s([a-z]):\s(.+)")?;
+
+		let mut policies = Vec::new();
+		for line in input.lines() {
+			let captures = re
+				.captures(line)
+				.ok_or_else(|| anyhow::anyhow!("regex on {} failed", line))?
+				.iter()
+				.flatten()
+				.skip(1)
+				.map(|cap| cap.as_str())
+				.collect::<Vec<_>>();
+
+			policies.push(Policy {
+				low: captures[0].parse::<usize>()?,
+				high: captures[1].parse::<usize>()?,
+				letter: captures[2].as_bytes()[0],
+				password: captures[3],
+			});
+		}
+
+		Ok(policies)
+	}
+
+	fn part_1(input: &Vec<Policy<'_>>) -> Result<usize> {
+		Ok(input
+			.iter()
+			.filter(|p| part_1_is_valid(p.low, p.high, p.letter, p.password.as_bytes()))
+			.count())
+	}
+
+	fn part_2(input: &Vec<Policy<'_>>) -> Result<usize> {
+		Ok(input
+			.iter()
+			.filter(|p| part_2_is_valid(p.low - 1, p.high - 1, p.letter, p.password.as_bytes()))
+			.count())
+	}
+}
+
+fn part_1_is_valid(low: usize, high: usize, letter: u8, password: &[u8]) -> bool {
+	let letter_count = password.iter().filter(|&&c| c == letter).count();
+	letter_count >= low && letter_count <= high
+}
+
+fn part_2_is_valid(first_idx: usize, second_idx: usize, letter: u8, password: &[u8]) -> bool {
+	let mut valid = password
+		.get(first_idx)
+		.map(|&v| v == letter)
+		.unwrap_or_default();
+	if password
+		.get(second_idx)
+		.map(|&v| v == letter)
+		.unwrap_or_default()
+	{
+		valid = !valid;
+	}
+	valid
+}
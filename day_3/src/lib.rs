@@ -0,0 +1,37 @@
+use anyhow::Result;
+use aoc_solution::Solution;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+	type Input<'a> = &'a str;
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Result<&str> {
+		Ok(input)
+	}
+
+	fn part_1(input: &&str) -> Result<usize> {
+		Ok(count_trees(input, 3, 1))
+	}
+
+	fn part_2(input: &&str) -> Result<usize> {
+		Ok([(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)]
+			.iter()
+			.copied()
+			.map(|(x_step, y_step)| count_trees(input, x_step, y_step))
+			.product())
+	}
+}
+
+fn count_trees(input: &str, x_step: usize, y_step: usize) -> usize {
+	input
+		.lines()
+		.map(str::as_bytes)
+		.step_by(y_step)
+		.enumerate()
+		.map(|(idx, line)| line[(idx * x_step) % line.len()])
+		.filter(|&c| c == b'#')
+		.count()
+}
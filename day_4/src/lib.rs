@@ -0,0 +1,101 @@
+use anyhow::Result;
+use aoc_solution::Solution;
+use itertools::Itertools;
+use std::collections::HashMap;
+
+pub struct Day4;
+
+impl Solution for Day4 {
+	type Input<'a> = Vec<HashMap<String, String>>;
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Result<Vec<HashMap<String, String>>> {
+		Ok(input
+			.lines()
+			.group_by(|line| line.is_empty())
+			.into_iter()
+			.map(|(_, grouped_lines)| {
+				grouped_lines
+					.flat_map(|line: &str| {
+						line.split(' ')
+							.flat_map(|pair| pair.split(':').map(ToOwned::to_owned).tuples())
+					})
+					.collect::<HashMap<_, _>>()
+			})
+			.collect())
+	}
+
+	fn part_1(input: &Vec<HashMap<String, String>>) -> Result<usize> {
+		Ok(input.iter().filter(|hm| is_valid_part_1(hm)).count())
+	}
+
+	fn part_2(input: &Vec<HashMap<String, String>>) -> Result<usize> {
+		Ok(input.iter().filter(|hm| is_valid_part_2(hm)).count())
+	}
+}
+
+fn is_valid_part_1(passport: &HashMap<String, String>) -> bool {
+	passport.contains_key("byr")
+		&& passport.contains_key("iyr")
+		&& passport.contains_key("eyr")
+		&& passport.contains_key("hgt")
+		&& passport.contains_key("hcl")
+		&& passport.contains_key("ecl")
+		&& passport.contains_key("pid")
+}
+
+fn is_valid_part_2(passport: &HashMap<String, String>) -> bool {
+	passport
+		.get("byr")
+		.and_then(|x| x.parse().ok())
+		.map(|x: u64| (1920..=2002).contains(&x))
+		.unwrap_or_default()
+		&& passport
+			.get("iyr")
+			.and_then(|x| x.parse().ok())
+			.map(|x: u64| (2010..=2020).contains(&x))
+			.unwrap_or_default()
+		&& passport
+			.get("eyr")
+			.and_then(|x| x.parse().ok())
+			.map(|x: u64| (2020..=2030).contains(&x))
+			.unwrap_or_default()
+		&& passport
+			.get("hgt")
+			.and_then(|x| {
+				x.strip_suffix("cm")
+					.and_then(|x| x.parse().ok())
+					.map(|x: u64| (150..=193).contains(&x))
+					.or_else(|| {
+						x.strip_suffix("in")
+							.and_then(|x| x.parse().ok())
+							.map(|x: u64| (59..=76).contains(&x))
+					})
+			})
+			.unwrap_or_default()
+		&& passport
+			.get("hcl")
+			.and_then(|x| x.strip_prefix("#"))
+			.filter(|x| x.len() == 6)
+			.map(|x| {
+				x.parse()
+					.ok()
+					.unwrap_or_else(|| x.bytes().all(|x| matches!(x, b'a'..=b'f' | b'0'..=b'9')))
+			})
+			.unwrap_or_default()
+		&& passport
+			.get("ecl")
+			.map(|x| {
+				matches!(
+					x.as_str(),
+					"amb" | "blu" | "brn" | "gry" | "grn" | "hzl" | "oth"
+				)
+			})
+			.unwrap_or_default()
+		&& passport
+			.get("pid")
+			.filter(|x| x.len() == 9)
+			.map(|x| x.parse::<u64>().is_ok())
+			.unwrap_or_default()
+}
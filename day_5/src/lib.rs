@@ -0,0 +1,63 @@
+use anyhow::Result;
+use aoc_solution::Solution;
+use itertools::Itertools;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+	type Input<'a> = Vec<(u8, u8, usize)>;
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Result<Vec<(u8, u8, usize)>> {
+		Ok(input.lines().map(str::as_bytes).map(decode).collect())
+	}
+
+	fn part_1(passes: &Vec<(u8, u8, usize)>) -> Result<usize> {
+		passes
+			.iter()
+			.map(|(_, _, seat_id)| *seat_id)
+			.max()
+			.ok_or_else(|| anyhow::anyhow!("part 1 fail"))
+	}
+
+	fn part_2(passes: &Vec<(u8, u8, usize)>) -> Result<usize> {
+		passes
+			.iter()
+			.copied()
+			.map(|(_, _, id)| id)
+			.sorted()
+			.tuple_windows::<(_, _)>()
+			.find(|(id1, id2)| id1 + 2 == *id2)
+			.map(|(id, _)| id + 1)
+			.ok_or_else(|| anyhow::anyhow!("part 2 fail"))
+	}
+}
+
+fn decode(pass: &[u8]) -> (u8, u8, usize) {
+	let pass = pass.iter().map(|c| match *c {
+		b'F' | b'L' => 0,
+		b'B' | b'R' => 1,
+		_ => panic!("bad input"),
+	});
+
+	let row = binary_search(pass.clone().take(7), 0_f32..=127_f32);
+	let column = binary_search(pass.skip(7), 0_f32..=7_f32);
+
+	(row, column, (row as usize * 8) + column as usize)
+}
+
+fn binary_search(iter: impl Iterator<Item = u8>, range: std::ops::RangeInclusive<f32>) -> u8 {
+	let (mut low, mut high) = range.into_inner();
+	iter.fold(0, |_result, c| match c {
+		0 => {
+			high -= ((high - low) / 2_f32).round();
+			high as u8
+		}
+		1 => {
+			low += ((high - low) / 2_f32).round();
+			low as u8
+		}
+		_ => panic!("bad input"),
+	})
+}
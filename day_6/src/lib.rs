@@ -0,0 +1,45 @@
+use anyhow::Result;
+use aoc_solution::Solution;
+use std::collections::HashMap;
+
+pub struct Day6;
+
+/// Number of people in a group and how many of them answered "yes" to each question
+type Group = (usize, HashMap<u8, usize>);
+
+impl Solution for Day6 {
+	type Input<'a> = Vec<Group>;
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Result<Vec<Group>> {
+		let mut groups = Vec::new();
+		for group in input.split("\n\n") {
+			let mut hm = HashMap::<u8, usize>::new();
+			let mut people_count = 0;
+			for person in group.lines() {
+				for answer in person.trim().as_bytes().iter().copied() {
+					*hm.entry(answer).or_insert(0) += 1;
+				}
+				people_count += 1;
+			}
+			groups.push((people_count, hm));
+		}
+		Ok(groups)
+	}
+
+	fn part_1(groups: &Vec<Group>) -> Result<usize> {
+		Ok(groups.iter().map(|(_, hm)| hm.keys().len()).sum())
+	}
+
+	fn part_2(groups: &Vec<Group>) -> Result<usize> {
+		Ok(groups
+			.iter()
+			.map(|(people_count, hm)| {
+				hm.iter()
+					.filter(|(_, answers)| **answers == *people_count)
+					.count()
+			})
+			.sum())
+	}
+}
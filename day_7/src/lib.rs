@@ -0,0 +1,81 @@
+use anyhow::Result;
+use aoc_solution::Solution;
+use itertools::Itertools;
+use regex::Regex;
+use std::collections::HashMap;
+
+type Bag<'a> = (&'a str, u64);
+type BagRule<'a> = HashMap<&'a str, Vec<Bag<'a>>>;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+	type Input<'a> = BagRule<'a>;
+	type Part1 = u64;
+	type Part2 = u64;
+
+	fn parse(input: &str) -> Result<BagRule<'_>> {
+		let re = Regex::new(
+			r"^(?P<bag>\w+ \w+) bags contain |(?:(?P<contains>no other bags.)|(?P<quantity>\d+) (?P<inside_bag_name>\w+ \w+) bag(?:s)?(?:, )?)",
+		)?;
+
+		let mut bags: BagRule = HashMap::new();
+		for line in input.lines() {
+			let captures = re.captures_iter(line).collect::<Vec<_>>();
+
+			let bag = captures[0].name("bag").unwrap().as_str();
+			let contains_bags = captures[1].name("contains").is_none();
+			if contains_bags {
+				for group in captures.into_iter().skip(1) {
+					let quantity = group
+						.name("quantity")
+						.unwrap()
+						.as_str()
+						.parse::<u64>()
+						.unwrap();
+					let inside_bag = group.name("inside_bag_name").unwrap().as_str();
+					bags.entry(bag).or_default().push((inside_bag, quantity));
+				}
+			}
+		}
+
+		Ok(bags)
+	}
+
+	fn part_1(bags: &BagRule<'_>) -> Result<u64> {
+		let mut part_1 = 0;
+		for (_, bags_inside) in bags.iter() {
+			let mut search_queue: Vec<&Bag> = Vec::new();
+			search_queue.extend(bags_inside);
+			let mut shiny_gold_count = 0;
+			while let Some((bag_name, _)) = search_queue.pop() {
+				if *bag_name == "shiny gold" {
+					shiny_gold_count += 1;
+				} else if let Some(v) = bags.get(bag_name) {
+					search_queue.extend(v);
+				}
+			}
+			if shiny_gold_count > 0 {
+				part_1 += 1;
+			}
+		}
+		Ok(part_1)
+	}
+
+	fn part_2(bags: &BagRule<'_>) -> Result<u64> {
+		let mut part_2: u64 = 0;
+		let mut search_queue: Vec<Bag> = Vec::new();
+		search_queue.extend(&bags["shiny gold"]);
+		while let Some((bag_name, quantity)) = search_queue.pop() {
+			part_2 += quantity;
+			if let Some(v) = bags.get(&bag_name) {
+				search_queue.extend(
+					v.iter()
+						.copied()
+						.update(|(_, v_quantity)| *v_quantity *= quantity),
+				);
+			}
+		}
+		Ok(part_2)
+	}
+}
@@ -0,0 +1,59 @@
+use anyhow::Result;
+use aoc_solution::Solution;
+use itertools::Itertools;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+	type Input<'a> = Vec<usize>;
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Result<Vec<usize>> {
+		input
+			.lines()
+			.map(str::parse)
+			.try_collect()
+			.map_err(Into::into)
+	}
+
+	fn part_1(input: &Vec<usize>) -> Result<usize> {
+		part_1(input)
+	}
+
+	fn part_2(input: &Vec<usize>) -> Result<usize> {
+		part_2(input)
+	}
+}
+
+fn part_1(input: &[usize]) -> Result<usize> {
+	let preamble_size = 25;
+
+	input
+		.iter()
+		.copied()
+		.enumerate()
+		.skip(preamble_size)
+		.find(|(idx, x)| {
+			!input[(idx - preamble_size)..=(idx - 1)]
+				.iter()
+				.copied()
+				.combinations(2)
+				.any(|comb| comb.into_iter().sum::<usize>() == *x)
+		})
+		.map(|(_, x)| x)
+		.ok_or_else(|| anyhow::anyhow!("Part 1 fail"))
+}
+
+fn part_2(input: &[usize]) -> Result<usize> {
+	let invalid_number = part_1(input)?;
+
+	(2..input.len())
+		.find_map(|i| {
+			input
+				.windows(i)
+				.find(|x| x.iter().sum::<usize>() == invalid_number)
+				.and_then(|x| x.iter().minmax().into_option().map(|(min, max)| min + max))
+		})
+		.ok_or_else(|| anyhow::anyhow!("Part 2 fail"))
+}